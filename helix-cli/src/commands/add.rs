@@ -61,6 +61,7 @@ pub async fn run(path: Option<String>, target: Option<AddTarget>) -> Result<()>
                     gateway_url: target.gateway_url,
                     query_auth_header: DEFAULT_QUERY_AUTH_HEADER.to_string(),
                     query_auth_env: DEFAULT_QUERY_AUTH_ENV.to_string(),
+                    query_auth_file: None,
                     availability_mode: None,
                     gateway_node_type: None,
                     db_node_type: None,
@@ -218,13 +218,7 @@ impl Credentials {
 /// If not authenticated, prompts the user to login interactively.
 /// Returns credentials if authenticated (or after successful login).
 pub async fn require_auth() -> Result<Credentials> {
-    let home = dirs::home_dir().ok_or_eyre("Cannot find home directory")?;
-    let credentials_path = home.join(".helix").join("credentials");
-
-    // Check if we have valid credentials
-    if let Some(credentials) = Credentials::try_read_from_file(&credentials_path)
-        && credentials.is_authenticated()
-    {
+    if let Some(credentials) = stored_credentials() {
         return Ok(credentials);
     }
 
@@ -234,6 +228,15 @@ pub async fn require_auth() -> Result<Credentials> {
     ))
 }
 
+/// Read saved Helix Cloud credentials without prompting or warning. Returns
+/// `None` when the user isn't logged in, so callers can treat Cloud metadata as
+/// optional.
+pub fn stored_credentials() -> Option<Credentials> {
+    let credentials_path = dirs::home_dir()?.join(".helix").join("credentials");
+    Credentials::try_read_from_file(&credentials_path)
+        .filter(|credentials| credentials.is_authenticated())
+}
+
 /// Ensure the user has Helix Cloud credentials, running the existing GitHub
 /// device login flow inline when credentials are missing or invalid.
 pub async fn ensure_auth_or_login() -> Result<Credentials> {
@@ -1267,6 +1267,7 @@ async fn seed_starter_data() -> Result<()> {
             None,
             None,
             None,
+            None,
             false,
             None,
             None,
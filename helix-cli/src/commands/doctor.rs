@@ -0,0 +1,380 @@
+use crate::commands::auth::stored_credentials;
+use crate::commands::query::resolve_query_auth;
+use crate::config::{ContainerRuntime, HelixConfig, undefined_env_refs};
+use crate::errors::CliError;
+use crate::local_runtime::LocalRuntime;
+use crate::output::symbols;
+use crate::port::is_port_available;
+use crate::project::{ProjectContext, find_project_root};
+use crate::utils::{command_exists, print_header, print_newline};
+use color_eyre::owo_colors::OwoColorize;
+use eyre::{Result, eyre};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Pass,
+    Fail,
+}
+
+/// One line of the `helix doctor` checklist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Check {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+    hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+pub async fn run() -> Result<()> {
+    let checks = collect_checks();
+
+    print_header("Helix Doctor");
+    for check in &checks {
+        print_check(check);
+    }
+    print_newline();
+
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        return Err(eyre!(
+            "{failed} check{} failed",
+            if failed == 1 { "" } else { "s" }
+        ));
+    }
+    println!("{} No problems found", symbols::SUCCESS.green().bold());
+    Ok(())
+}
+
+fn collect_checks() -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let root = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| find_project_root(&cwd).ok());
+    let Some(root) = root else {
+        checks.push(Check::fail(
+            "helix.toml",
+            "no helix.toml found in this directory or its parents",
+            "run `helix init` to create a project, or cd into one",
+        ));
+        checks.push(runtime_check(
+            ContainerRuntime::default(),
+            command_exists(ContainerRuntime::default().binary()),
+            LocalRuntime::is_running(ContainerRuntime::default()),
+        ));
+        return checks;
+    };
+
+    let config_path = root.join("helix.toml");
+    let missing = std::fs::read_to_string(&config_path)
+        .map(|content| undefined_env_refs(&content, |name| std::env::var(name).ok()))
+        .unwrap_or_default();
+    checks.extend(env_checks(&missing));
+
+    // Undefined references were reported above; load the rest of helix.toml anyway so
+    // the checks below still run.
+    let (config, unresolved) = match HelixConfig::from_file_skipping_undefined_env(&config_path) {
+        Ok((config, unresolved)) => {
+            checks.push(Check::pass("helix.toml", config_path.display().to_string()));
+            (Some(config), unresolved)
+        }
+        Err(error) => {
+            let error = error.to_cli_error();
+            checks.push(Check::fail(
+                "helix.toml",
+                error.message,
+                error
+                    .hint
+                    .unwrap_or_else(|| "fix helix.toml and run `helix doctor` again".into()),
+            ));
+            (None, Vec::new())
+        }
+    };
+
+    let runtime = config
+        .as_ref()
+        .map_or_else(ContainerRuntime::default, |config| {
+            config.project.container_runtime
+        });
+    let runtime_running = LocalRuntime::is_running(runtime);
+    if config
+        .as_ref()
+        .is_none_or(|config| !config.local.is_empty())
+    {
+        checks.push(runtime_check(
+            runtime,
+            command_exists(runtime.binary()),
+            runtime_running,
+        ));
+    }
+
+    let Some(config) = config else {
+        return checks;
+    };
+    let project = ProjectContext {
+        helix_dir: root.join(".helix"),
+        root,
+        config,
+    };
+
+    let local_runtime = LocalRuntime::new(&project);
+    let mut local: Vec<_> = project.config.local.iter().collect();
+    local.sort_by_key(|(name, _)| *name);
+    // A port from an undefined variable is the default, not the one the instance uses.
+    let port_known = |name: &str| !unresolved.contains(&format!("local.{name}.port"));
+    for (name, config) in local.into_iter().filter(|(name, _)| port_known(name)) {
+        let available = is_port_available(config.port);
+        let owned_by_instance = !available
+            && runtime_running
+            && local_runtime
+                .status(name)
+                .ok()
+                .flatten()
+                .is_some_and(|status| status.status.starts_with("Up"));
+        checks.push(port_check(name, config.port, available, owned_by_instance));
+    }
+
+    if !project.config.enterprise.is_empty() {
+        checks.push(cloud_auth_check(stored_credentials().is_some()));
+
+        // `helix query` also reads the key from a project-root .env.
+        let dotenv: HashMap<String, String> = dotenvy::from_path_iter(project.root.join(".env"))
+            .map(|entries| entries.flatten().collect())
+            .unwrap_or_default();
+        let lookup = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .or_else(|| dotenv.get(name).cloned())
+        };
+        let mut enterprise: Vec<_> = project.config.enterprise.iter().collect();
+        enterprise.sort_by_key(|(name, _)| *name);
+        for (name, config) in enterprise {
+            checks.push(query_auth_check(
+                name,
+                resolve_query_auth(config, &project.root, lookup).map(|_| ()),
+            ));
+        }
+    }
+
+    checks
+}
+
+fn runtime_check(runtime: ContainerRuntime, installed: bool, running: bool) -> Check {
+    let name = runtime.label();
+    match (installed, running) {
+        (_, true) => Check::pass(name, "installed and running"),
+        (true, false) => Check::fail(
+            name,
+            "installed but the daemon is not running",
+            format!(
+                "start {name} (macOS: `open -a Docker`, `colima start` or `podman machine start`; \
+                 Linux: `sudo systemctl start docker`)"
+            ),
+        ),
+        (false, false) => Check::fail(
+            name,
+            format!("`{}` was not found on PATH", runtime.binary()),
+            format!("install {name}, or set `container_runtime` in helix.toml"),
+        ),
+    }
+}
+
+/// One failing check per `${VAR}` reference in helix.toml that has no default
+/// and isn't set. API keys and other secrets are referenced this way.
+fn env_checks(missing: &[String]) -> Vec<Check> {
+    missing
+        .iter()
+        .map(|name| {
+            Check::fail(
+                format!("${name}"),
+                "referenced in helix.toml but not set",
+                format!("export {name}=..., or give it a default with `${{{name}:-value}}`"),
+            )
+        })
+        .collect()
+}
+
+fn port_check(instance: &str, port: u16, available: bool, owned_by_instance: bool) -> Check {
+    let name = format!("port {port}");
+    if available {
+        Check::pass(name, format!("free for local instance '{instance}'"))
+    } else if owned_by_instance {
+        Check::pass(name, format!("in use by running instance '{instance}'"))
+    } else {
+        Check::fail(
+            name,
+            format!("in use by another process; '{instance}' can't start on it"),
+            format!(
+                "stop whatever is listening on {port}, or run `helix start {instance} --port <port>`"
+            ),
+        )
+    }
+}
+
+fn cloud_auth_check(logged_in: bool) -> Check {
+    if logged_in {
+        Check::pass("Helix Cloud", "logged in")
+    } else {
+        Check::fail(
+            "Helix Cloud",
+            "not logged in; Enterprise instances need Cloud credentials",
+            "run `helix auth login`",
+        )
+    }
+}
+
+/// Whether `helix query` can find the query API key for an Enterprise instance.
+fn query_auth_check(instance: &str, resolved: Result<()>) -> Check {
+    let name = format!("query auth '{instance}'");
+    let error = match resolved {
+        Ok(()) => return Check::pass(name, "query API key found"),
+        Err(error) => error,
+    };
+    let (detail, hint) = match error.downcast_ref::<CliError>() {
+        Some(error) => (
+            error.message.clone(),
+            error.hint.clone().or_else(|| error.caused_by.clone()),
+        ),
+        None => (error.to_string(), None),
+    };
+    Check::fail(
+        name,
+        detail,
+        hint.unwrap_or_else(|| "check `query_auth_file` in helix.toml".into()),
+    )
+}
+
+fn print_check(check: &Check) {
+    let symbol = match check.status {
+        CheckStatus::Pass => symbols::SUCCESS.green().bold().to_string(),
+        CheckStatus::Fail => symbols::FAILURE.red().bold().to_string(),
+    };
+    println!(
+        "  {symbol} {}: {}",
+        check.name.bright_white().bold(),
+        check.detail
+    );
+    if let Some(hint) = &check.hint {
+        println!("    {} {}", symbols::INFO.blue(), hint.dimmed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::EnterpriseInstanceConfig;
+    use std::path::Path;
+
+    #[test]
+    fn missing_env_reference_produces_a_fail_entry() {
+        let content = r#"
+[project]
+name = "demo"
+
+[local.dev]
+image = "${HELIX_IMAGE:-ghcr.io/helixdb/enterprise-dev}"
+tag = "${HELIX_TAG}"
+
+[enterprise.prod]
+cluster_id = "${PROD_CLUSTER}-${HELIX_TAG}"
+"#;
+        let checks = env_checks(&undefined_env_refs(content, |_| None));
+
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|check| check.status == CheckStatus::Fail));
+        assert_eq!(checks[0].name, "$HELIX_TAG");
+        assert_eq!(checks[1].name, "$PROD_CLUSTER");
+
+        let set = |name: &str| (name != "HELIX_IMAGE").then(|| "value".to_string());
+        assert!(env_checks(&undefined_env_refs(content, set)).is_empty());
+    }
+
+    #[test]
+    fn query_auth_check_reports_missing_key_without_leaking_it() {
+        let config: EnterpriseInstanceConfig = toml::from_str(
+            r#"
+cluster_id = "cluster-123"
+query_auth_env = "PROD_API_KEY"
+"#,
+        )
+        .unwrap();
+        let root = Path::new("/nonexistent");
+
+        let found = resolve_query_auth(&config, root, |name| {
+            (name == "PROD_API_KEY").then(|| "secret-key".to_string())
+        });
+        let check = query_auth_check("prod", found.map(|_| ()));
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert!(!check.detail.contains("secret-key"));
+
+        let missing = query_auth_check(
+            "prod",
+            resolve_query_auth(&config, root, |_| None).map(|_| ()),
+        );
+        assert_eq!(missing.status, CheckStatus::Fail);
+        assert_eq!(missing.name, "query auth 'prod'");
+        assert!(missing.detail.contains("PROD_API_KEY"));
+        assert!(missing.hint.unwrap().contains("PROD_API_KEY_FILE"));
+    }
+
+    #[test]
+    fn runtime_check_distinguishes_missing_and_stopped() {
+        let docker = ContainerRuntime::Docker;
+        assert_eq!(runtime_check(docker, true, true).status, CheckStatus::Pass);
+
+        let stopped = runtime_check(docker, true, false);
+        assert_eq!(stopped.status, CheckStatus::Fail);
+        assert!(stopped.detail.contains("not running"));
+
+        let missing = runtime_check(docker, false, false);
+        assert_eq!(missing.status, CheckStatus::Fail);
+        assert!(missing.detail.contains("not found"));
+    }
+
+    #[test]
+    fn port_check_fails_only_for_foreign_listeners() {
+        assert_eq!(
+            port_check("dev", 6969, true, false).status,
+            CheckStatus::Pass
+        );
+        assert_eq!(
+            port_check("dev", 6969, false, true).status,
+            CheckStatus::Pass
+        );
+        let busy = port_check("dev", 6969, false, false);
+        assert_eq!(busy.status, CheckStatus::Fail);
+        assert!(busy.hint.unwrap().contains("6969"));
+    }
+
+    #[test]
+    fn cloud_auth_check_requires_login() {
+        assert_eq!(cloud_auth_check(true).status, CheckStatus::Pass);
+        let logged_out = cloud_auth_check(false);
+        assert_eq!(logged_out.status, CheckStatus::Fail);
+        assert_eq!(logged_out.hint.as_deref(), Some("run `helix auth login`"));
+    }
+}
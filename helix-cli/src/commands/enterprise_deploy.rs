@@ -35,12 +35,100 @@ pub(crate) async fn deploy_enterprise_by_cluster_id(
     deploy_enterprise(project, instance_name, config).await
 }
 
+/// Everything `helix push` would send to Helix Cloud for one instance. Building
+/// a plan compiles the query project but has no remote side effects, which is
+/// what `helix push --dry-run` reports.
+pub(crate) struct EnterpriseDeployPlan {
+    pub(crate) instance_name: String,
+    pub(crate) cluster_id: String,
+    pub(crate) deploy_url: String,
+    pub(crate) queries_json_size_bytes: usize,
+    pub(crate) source_files: Vec<String>,
+    /// The applied config with `${VAR}` references in place of their resolved
+    /// values; the payload carries the resolved config.
+    pub(crate) helix_toml: Option<String>,
+    pub(crate) payload: Vec<u8>,
+}
+
+impl EnterpriseDeployPlan {
+    /// Human-readable summary of the planned deploy, one line per action.
+    pub(crate) fn summary_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "Deploy instance '{}' to cluster {}",
+                self.instance_name, self.cluster_id
+            ),
+            format!("POST {}", self.deploy_url),
+            format!(
+                "Upload queries.json ({} bytes)",
+                self.queries_json_size_bytes
+            ),
+            format!(
+                "Upload {} source file(s): {}",
+                self.source_files.len(),
+                self.source_files.join(", ")
+            ),
+            format!(
+                "Request payload: {} bytes (limit {} bytes)",
+                self.payload.len(),
+                ENTERPRISE_DEPLOY_REQUEST_MAX_BYTES
+            ),
+        ];
+        match &self.helix_toml {
+            Some(helix_toml) => {
+                lines.push("Apply helix.toml:".to_string());
+                lines.extend(helix_toml.lines().map(|line| format!("  {line}")));
+            }
+            None => lines.push("No helix.toml will be applied".to_string()),
+        }
+        lines
+    }
+}
+
 pub(crate) async fn deploy_enterprise(
     project: &ProjectContext,
     instance_name: &str,
     config: &EnterpriseInstanceConfig,
 ) -> Result<()> {
     let credentials = require_auth().await?;
+    let plan = prepare_enterprise_deploy(project, instance_name, config)?;
+
+    let response = reqwest::Client::new()
+        .post(&plan.deploy_url)
+        .header("x-api-key", &credentials.helix_admin_key)
+        .header("Content-Type", "application/json")
+        .body(plan.payload)
+        .send()
+        .await
+        .map_err(|e| eyre!("Enterprise deployment request failed: {e}"))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(eyre!("Enterprise deployment failed ({status}): {body}"));
+    }
+
+    let response_payload: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| eyre!("Failed to parse enterprise deploy response: {e}"))?;
+    if let Some(s3_key) = response_payload
+        .get("s3_key")
+        .and_then(|value| value.as_str())
+    {
+        output::info(&format!("Uploaded queries.json to {s3_key}"));
+    }
+
+    output::success("Enterprise cluster deployed successfully");
+    Ok(())
+}
+
+/// Compile the query project and assemble the deploy request without sending it.
+pub(crate) fn prepare_enterprise_deploy(
+    project: &ProjectContext,
+    instance_name: &str,
+    config: &EnterpriseInstanceConfig,
+) -> Result<EnterpriseDeployPlan> {
     let queries_project_dir = enterprise_queries_dir(project);
     let query_json_path = compile_enterprise_queries(&queries_project_dir)?;
     let query_json_bytes = std::fs::read(&query_json_path).map_err(|e| {
@@ -65,10 +153,35 @@ pub(crate) async fn deploy_enterprise(
         ));
     }
 
-    let helix_toml_content = pruned_enterprise_config(project, instance_name, config)
-        .and_then(|config| toml::to_string_pretty(&config).ok());
+    build_enterprise_deploy_plan(
+        project,
+        instance_name,
+        config,
+        &query_json_bytes,
+        source_files,
+    )
+}
+
+fn build_enterprise_deploy_plan(
+    project: &ProjectContext,
+    instance_name: &str,
+    config: &EnterpriseInstanceConfig,
+    query_json_bytes: &[u8],
+    source_files: HashMap<String, String>,
+) -> Result<EnterpriseDeployPlan> {
+    // The cluster can't see the local environment, so the uploaded config carries
+    // resolved values; only the printed preview keeps the `${VAR}` references.
+    let pruned_config = pruned_enterprise_config(project, instance_name, config);
+    let helix_toml_content = pruned_config
+        .as_ref()
+        .and_then(|config| toml::to_string_pretty(config).ok());
+    let helix_toml_preview = pruned_config
+        .as_ref()
+        .and_then(|config| config.to_toml_with_env_refs().ok());
+    let mut source_file_names: Vec<String> = source_files.keys().cloned().collect();
+    source_file_names.sort();
     let payload = json!({
-        "queries_json_b64": BASE64_STANDARD.encode(&query_json_bytes),
+        "queries_json_b64": BASE64_STANDARD.encode(query_json_bytes),
         "queries_json_size_bytes": query_json_bytes.len(),
         "source_files": source_files,
         "instance_name": instance_name,
@@ -85,39 +198,19 @@ pub(crate) async fn deploy_enterprise(
         ));
     }
 
-    let deploy_url = format!(
-        "{}/api/cli/enterprise-clusters/{}/deploy",
-        cloud_base_url(),
-        config.cluster_id
-    );
-    let response = reqwest::Client::new()
-        .post(&deploy_url)
-        .header("x-api-key", &credentials.helix_admin_key)
-        .header("Content-Type", "application/json")
-        .body(payload_bytes)
-        .send()
-        .await
-        .map_err(|e| eyre!("Enterprise deployment request failed: {e}"))?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let body = response.text().await.unwrap_or_default();
-        return Err(eyre!("Enterprise deployment failed ({status}): {body}"));
-    }
-
-    let response_payload: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| eyre!("Failed to parse enterprise deploy response: {e}"))?;
-    if let Some(s3_key) = response_payload
-        .get("s3_key")
-        .and_then(|value| value.as_str())
-    {
-        output::info(&format!("Uploaded queries.json to {s3_key}"));
-    }
-
-    output::success("Enterprise cluster deployed successfully");
-    Ok(())
+    Ok(EnterpriseDeployPlan {
+        instance_name: instance_name.to_string(),
+        cluster_id: config.cluster_id.clone(),
+        deploy_url: format!(
+            "{}/api/cli/enterprise-clusters/{}/deploy",
+            cloud_base_url(),
+            config.cluster_id
+        ),
+        queries_json_size_bytes: query_json_bytes.len(),
+        source_files: source_file_names,
+        helix_toml: helix_toml_preview,
+        payload: payload_bytes,
+    })
 }
 
 pub(crate) fn enterprise_queries_dir(project: &ProjectContext) -> PathBuf {
@@ -188,6 +281,8 @@ fn pruned_enterprise_config(
         project: project.config.project.clone(),
         local: HashMap::new(),
         enterprise,
+        // Templates for instances that were pruned have no path to restore to.
+        env_templates: project.config.env_templates.clone(),
     })
 }
 
@@ -296,6 +391,107 @@ pub(crate) fn collect_enterprise_source_files(
 mod tests {
     use super::*;
 
+    fn project_with_enterprise_instance() -> ProjectContext {
+        let config: HelixConfig = toml::from_str(
+            r#"
+[project]
+name = "demo"
+
+[local.dev]
+
+[enterprise.production]
+cluster_id = "cluster-123"
+"#,
+        )
+        .unwrap();
+        ProjectContext {
+            root: PathBuf::from("/tmp/demo"),
+            config,
+            helix_dir: PathBuf::from("/tmp/demo/.helix"),
+        }
+    }
+
+    #[test]
+    fn deploy_plan_describes_upload_and_applied_config() {
+        let project = project_with_enterprise_instance();
+        let config = project.config.enterprise.get("production").unwrap();
+        let source_files = HashMap::from([
+            ("src/main.rs".to_string(), "fn main() {}".to_string()),
+            ("Cargo.toml".to_string(), "[package]".to_string()),
+        ]);
+
+        let plan = build_enterprise_deploy_plan(
+            &project,
+            "production",
+            config,
+            br#"{"queries":[]}"#,
+            source_files,
+        )
+        .unwrap();
+
+        assert_eq!(plan.cluster_id, "cluster-123");
+        assert!(
+            plan.deploy_url
+                .ends_with("/api/cli/enterprise-clusters/cluster-123/deploy")
+        );
+        assert_eq!(plan.queries_json_size_bytes, 14);
+        assert_eq!(plan.source_files, vec!["Cargo.toml", "src/main.rs"]);
+
+        // The applied config only carries the deployed instance.
+        let helix_toml = plan.helix_toml.as_deref().unwrap();
+        assert!(helix_toml.contains("[enterprise.production]"));
+        assert!(!helix_toml.contains("[local.dev]"));
+
+        let summary = plan.summary_lines().join("\n");
+        assert!(summary.contains("Deploy instance 'production' to cluster cluster-123"));
+        assert!(summary.contains("Upload 2 source file(s): Cargo.toml, src/main.rs"));
+        assert!(summary.contains("Apply helix.toml:"));
+    }
+
+    #[test]
+    fn deploy_plan_summary_redacts_env_references() {
+        let config = HelixConfig::parse_with_env(
+            r#"
+[project]
+name = "demo"
+
+[enterprise.production]
+cluster_id = "cluster-123"
+gateway_url = "https://${GATEWAY_HOST}"
+"#,
+            Path::new("helix.toml"),
+            |name| (name == "GATEWAY_HOST").then(|| "gw.internal.example".to_string()),
+        )
+        .unwrap();
+        let project = ProjectContext {
+            root: PathBuf::from("/tmp/demo"),
+            config,
+            helix_dir: PathBuf::from("/tmp/demo/.helix"),
+        };
+        let config = project.config.enterprise.get("production").unwrap();
+
+        let plan = build_enterprise_deploy_plan(
+            &project,
+            "production",
+            config,
+            br#"{"queries":[]}"#,
+            HashMap::from([("Cargo.toml".to_string(), "[package]".to_string())]),
+        )
+        .unwrap();
+
+        let summary = plan.summary_lines().join("\n");
+        assert!(summary.contains("https://${GATEWAY_HOST}"));
+        assert!(!summary.contains("gw.internal.example"));
+        assert!(!plan.helix_toml.unwrap().contains("gw.internal.example"));
+        let payload: serde_json::Value = serde_json::from_slice(&plan.payload).unwrap();
+        assert!(
+            payload["helix_toml"]
+                .as_str()
+                .unwrap()
+                .contains("https://gw.internal.example")
+        );
+    }
+
     #[test]
     fn include_rules_allow_only_expected_enterprise_project_files() {
         assert!(should_include_enterprise_source_file(Path::new(
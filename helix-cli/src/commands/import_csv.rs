@@ -0,0 +1,478 @@
+use crate::commands::query::send_query;
+use crate::output::{self, Operation, Step};
+use crate::project::ProjectContext;
+use eyre::{Result, eyre};
+use serde_json::{Map, Value, json};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Node properties built from one CSV record.
+type Row = Map<String, Value>;
+
+/// Rows sent per write request unless `--batch-size` says otherwise.
+pub const DEFAULT_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldType {
+    String,
+    I64,
+    F64,
+    Bool,
+}
+
+impl FieldType {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "string" | "str" => Some(Self::String),
+            "i64" | "int" | "integer" => Some(Self::I64),
+            "f64" | "float" | "number" => Some(Self::F64),
+            "bool" | "boolean" => Some(Self::Bool),
+            _ => None,
+        }
+    }
+
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::I64 => "i64",
+            Self::F64 => "f64",
+            Self::Bool => "bool",
+        }
+    }
+
+    /// Empty cells are null except in string columns, where they stay `""`.
+    fn coerce(&self, raw: &str) -> Result<Value, String> {
+        let trimmed = raw.trim();
+        let invalid = || format!("expected {}, got '{raw}'", self.as_str());
+        if trimmed.is_empty() && *self != Self::String {
+            return Ok(Value::Null);
+        }
+        match self {
+            Self::String => Ok(Value::String(raw.to_string())),
+            Self::I64 => trimmed
+                .parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| invalid()),
+            Self::F64 => trimmed
+                .parse::<f64>()
+                .ok()
+                .and_then(|number| serde_json::Number::from_f64(number).map(Value::Number))
+                .ok_or_else(invalid),
+            Self::Bool => match trimmed.to_ascii_lowercase().as_str() {
+                "true" | "1" | "yes" => Ok(Value::Bool(true)),
+                "false" | "0" | "no" => Ok(Value::Bool(false)),
+                _ => Err(invalid()),
+            },
+        }
+    }
+}
+
+/// One `--map column=field[:type]` flag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ColumnMapping {
+    column: String,
+    field: String,
+    field_type: FieldType,
+}
+
+impl ColumnMapping {
+    fn parse(spec: &str) -> Result<Self> {
+        let (column, target) = spec.split_once('=').ok_or_else(|| {
+            eyre!("invalid --map '{spec}': expected column=field or column=field:type")
+        })?;
+        let (field, field_type) = match target.rsplit_once(':') {
+            Some((field, type_name)) => {
+                let field_type = FieldType::parse(type_name).ok_or_else(|| {
+                    eyre!(
+                        "invalid --map '{spec}': unknown type '{type_name}' (use string, i64, f64 or bool)"
+                    )
+                })?;
+                (field, field_type)
+            }
+            None => (target, FieldType::String),
+        };
+        if column.trim().is_empty() || field.trim().is_empty() {
+            return Err(eyre!(
+                "invalid --map '{spec}': column and field must not be empty"
+            ));
+        }
+        Ok(Self {
+            column: column.trim().to_string(),
+            field: field.trim().to_string(),
+            field_type,
+        })
+    }
+}
+
+/// A CSV row that was skipped because a value didn't coerce to its field type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RowError {
+    line: u64,
+    message: String,
+}
+
+pub async fn run(
+    instance: String,
+    file: String,
+    label: String,
+    map: Vec<String>,
+    batch_size: usize,
+) -> Result<()> {
+    let project = ProjectContext::find_and_load(None)?;
+    // Same as `helix query`: Enterprise query auth may live in a project-root .env.
+    let _ = dotenvy::from_path(project.root.join(".env"));
+    project.config.get_instance(&instance)?;
+
+    if label.trim().is_empty() {
+        return Err(eyre!("--label must not be empty"));
+    }
+    if batch_size == 0 {
+        return Err(eyre!("--batch-size must be at least 1"));
+    }
+    let mappings = map
+        .iter()
+        .map(|spec| ColumnMapping::parse(spec))
+        .collect::<Result<Vec<_>>>()?;
+
+    let file = Path::new(&file);
+    let reader =
+        File::open(file).map_err(|e| eyre!("Failed to open CSV file {}: {e}", file.display()))?;
+    let mut batches = RowBatches::new(reader, &mappings, batch_size)?;
+    let fields = batches.field_names();
+
+    let op = Operation::new("Importing", &file.display().to_string());
+    let mut inserted = 0;
+    let mut skipped = 0;
+    while let Some(batch) = batches.next() {
+        for error in batches.take_errors() {
+            output::warning(&format!("Skipping line {}: {}", error.line, error.message));
+            skipped += 1;
+        }
+        let batch = match batch {
+            Ok(batch) => batch,
+            Err(error) => {
+                op.failure();
+                return Err(error.wrap_err(format!(
+                    "Import stopped; {inserted} rows were already inserted"
+                )));
+            }
+        };
+
+        let first = inserted + 1;
+        let last = inserted + batch.len();
+        let mut step = Step::with_messages(
+            &format!("Inserting rows {first}-{last}"),
+            &format!("Inserted rows {first}-{last}"),
+        );
+        step.start();
+        let request = insert_request(&label, &fields, &batch);
+        if let Err(error) = send_query(&project, &instance, &request, false, None, None).await {
+            step.fail();
+            op.failure();
+            return Err(error.wrap_err(format!(
+                "Import stopped; rows before {first} were already inserted"
+            )));
+        }
+        step.done();
+        inserted = last;
+    }
+    for error in batches.take_errors() {
+        output::warning(&format!("Skipping line {}: {}", error.line, error.message));
+        skipped += 1;
+    }
+    if inserted == 0 {
+        op.failure();
+        return Err(eyre!("No rows to import from {}", file.display()));
+    }
+
+    op.success();
+    Operation::print_details(&[
+        ("Label", &label),
+        ("Inserted", &inserted.to_string()),
+        ("Skipped", &skipped.to_string()),
+    ]);
+    Ok(())
+}
+
+/// Reads CSV records lazily and yields them as node property objects, at most
+/// `batch_size` per batch, so a file is never held in memory whole. With no
+/// mappings, every column is imported as a string field of the same name. Rows
+/// with a value that doesn't coerce are skipped and collected for
+/// [`take_errors`](Self::take_errors) instead of failing the whole import.
+struct RowBatches<R: Read> {
+    records: csv::StringRecordsIntoIter<R>,
+    mappings: Vec<ColumnMapping>,
+    columns: Vec<usize>,
+    batch_size: usize,
+    errors: Vec<RowError>,
+}
+
+impl<R: Read> RowBatches<R> {
+    fn new(reader: R, mappings: &[ColumnMapping], batch_size: usize) -> Result<Self> {
+        let mut reader = csv::Reader::from_reader(reader);
+        let headers = reader.headers()?.clone();
+
+        let mappings: Vec<ColumnMapping> = if mappings.is_empty() {
+            headers
+                .iter()
+                .map(|header| ColumnMapping {
+                    column: header.to_string(),
+                    field: header.to_string(),
+                    field_type: FieldType::String,
+                })
+                .collect()
+        } else {
+            mappings.to_vec()
+        };
+        let columns = mappings
+            .iter()
+            .map(|mapping| {
+                headers
+                    .iter()
+                    .position(|header| header == mapping.column)
+                    .ok_or_else(|| {
+                        eyre!(
+                            "CSV has no column '{}' (columns: {})",
+                            mapping.column,
+                            headers.iter().collect::<Vec<_>>().join(", ")
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            records: reader.into_records(),
+            mappings,
+            columns,
+            batch_size,
+            errors: Vec::new(),
+        })
+    }
+
+    /// Fields set on every node, in mapping order.
+    fn field_names(&self) -> Vec<String> {
+        self.mappings
+            .iter()
+            .map(|mapping| mapping.field.clone())
+            .collect()
+    }
+
+    /// Rows skipped since the last call.
+    fn take_errors(&mut self) -> Vec<RowError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    fn map_record(&self, record: &csv::StringRecord) -> Result<Row, String> {
+        let mut row = Row::new();
+        for (mapping, &column) in self.mappings.iter().zip(&self.columns) {
+            let raw = record.get(column).unwrap_or_default();
+            let value = mapping
+                .field_type
+                .coerce(raw)
+                .map_err(|reason| format!("column '{}': {reason}", mapping.column))?;
+            row.insert(mapping.field.clone(), value);
+        }
+        Ok(row)
+    }
+}
+
+impl<R: Read> Iterator for RowBatches<R> {
+    type Item = Result<Vec<Row>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch = Vec::with_capacity(self.batch_size);
+        while batch.len() < self.batch_size {
+            let record = match self.records.next() {
+                Some(Ok(record)) => record,
+                Some(Err(error)) => return Some(Err(error.into())),
+                None => break,
+            };
+            match self.map_record(&record) {
+                Ok(row) => batch.push(row),
+                Err(message) => self.errors.push(RowError {
+                    line: record.position().map_or(0, |position| position.line()),
+                    message,
+                }),
+            }
+        }
+        (!batch.is_empty()).then_some(Ok(batch))
+    }
+}
+
+/// A write request that adds one `label` node per row, in the same `ForEach` +
+/// `AddN` shape as the starter seed query. Nothing is returned, so the server
+/// doesn't serialize every created node back.
+fn insert_request(label: &str, fields: &[String], rows: &[Row]) -> Value {
+    let properties: Vec<Value> = fields
+        .iter()
+        .map(|field| json!([field, {"Expr": {"Param": field}}]))
+        .collect();
+    json!({
+        "request_type": "write",
+        "query": {
+            "queries": [
+                {"ForEach": {
+                    "param": "data",
+                    "body": [
+                        {"Query": {
+                            "name": "created",
+                            "steps": [
+                                {"AddN": {
+                                    "label": label,
+                                    "properties": properties
+                                }}
+                            ],
+                            "condition": null
+                        }}
+                    ]
+                }}
+            ],
+            "returns": []
+        },
+        "parameters": {"data": rows},
+        "parameter_types": {"data": {"Array": "Object"}}
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEOPLE: &str = "name,age,score,active\n\
+                          Ada,36,9.5,true\n\
+                          Grace,not-a-number,8.0,yes\n\
+                          Katherine,101,7.25,0\n";
+
+    fn people_mappings() -> Vec<ColumnMapping> {
+        [
+            "name=fullName",
+            "age=age:i64",
+            "score=score:f64",
+            "active=active:bool",
+        ]
+        .into_iter()
+        .map(|spec| ColumnMapping::parse(spec).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn mappings_parse_field_and_type() {
+        assert_eq!(
+            ColumnMapping::parse("Age=age:int").unwrap(),
+            ColumnMapping {
+                column: "Age".into(),
+                field: "age".into(),
+                field_type: FieldType::I64,
+            }
+        );
+        assert_eq!(
+            ColumnMapping::parse("name=name").unwrap().field_type,
+            FieldType::String
+        );
+        assert!(ColumnMapping::parse("name").is_err());
+        assert!(ColumnMapping::parse("=name").is_err());
+        assert!(ColumnMapping::parse("age=age:uuid").is_err());
+    }
+
+    /// Every row and skipped-row error from `csv`, read in batches of `batch_size`.
+    fn read_all(
+        csv: &str,
+        mappings: &[ColumnMapping],
+        batch_size: usize,
+    ) -> (Vec<Vec<Row>>, Vec<RowError>) {
+        let mut batches = RowBatches::new(csv.as_bytes(), mappings, batch_size).unwrap();
+        let rows = batches.by_ref().collect::<Result<Vec<_>>>().unwrap();
+        (rows, batches.take_errors())
+    }
+
+    #[test]
+    fn records_are_mapped_and_coerced() {
+        let (batches, errors) = read_all(PEOPLE, &people_mappings(), 10);
+        let rows = batches.concat();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            Value::Object(rows[0].clone()),
+            json!({"fullName": "Ada", "age": 36, "score": 9.5, "active": true})
+        );
+        assert_eq!(
+            Value::Object(rows[1].clone()),
+            json!({"fullName": "Katherine", "age": 101, "score": 7.25, "active": false})
+        );
+
+        assert_eq!(
+            errors,
+            vec![RowError {
+                line: 3,
+                message: "column 'age': expected i64, got 'not-a-number'".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn rows_are_read_in_batches() {
+        let (batches, _) = read_all(PEOPLE, &[], 2);
+        let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![2, 1]);
+    }
+
+    #[test]
+    fn empty_typed_cells_import_as_null() {
+        let csv = "name,age,active\nAda,,\n,36,true\n";
+        let (batches, errors) = read_all(csv, &people_mappings()[..1], 10);
+        assert!(errors.is_empty());
+        assert_eq!(batches[0][1]["fullName"], json!(""));
+
+        let mappings = ["name=name", "age=age:i64", "active=active:bool"]
+            .into_iter()
+            .map(|spec| ColumnMapping::parse(spec).unwrap())
+            .collect::<Vec<_>>();
+        let (batches, errors) = read_all(csv, &mappings, 10);
+        assert!(errors.is_empty());
+        assert_eq!(
+            Value::Object(batches[0][0].clone()),
+            json!({"name": "Ada", "age": null, "active": null})
+        );
+    }
+
+    #[test]
+    fn unmapped_import_keeps_every_column_as_string() {
+        let (batches, errors) = read_all(PEOPLE, &[], 10);
+        let rows = batches.concat();
+
+        assert!(errors.is_empty());
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1]["age"], json!("not-a-number"));
+    }
+
+    #[test]
+    fn unknown_column_lists_available_headers() {
+        let mapping = ColumnMapping::parse("email=email").unwrap();
+        let Err(err) = RowBatches::new(PEOPLE.as_bytes(), &[mapping], 10) else {
+            panic!("expected an unknown column error");
+        };
+        assert!(err.to_string().contains("no column 'email'"));
+        assert!(err.to_string().contains("name, age, score, active"));
+    }
+
+    #[test]
+    fn insert_request_adds_one_node_per_row() {
+        let mappings = people_mappings();
+        let mut batches = RowBatches::new(PEOPLE.as_bytes(), &mappings, 10).unwrap();
+        let fields = batches.field_names();
+        let rows = batches.next().unwrap().unwrap();
+        let request = insert_request("Person", &fields, &rows);
+
+        let add =
+            &request["query"]["queries"][0]["ForEach"]["body"][0]["Query"]["steps"][0]["AddN"];
+        assert_eq!(add["label"], "Person");
+        assert_eq!(
+            add["properties"][1],
+            json!(["age", {"Expr": {"Param": "age"}}])
+        );
+        assert_eq!(request["parameters"]["data"].as_array().unwrap().len(), 2);
+        assert_eq!(request["request_type"], "write");
+        assert_eq!(request["query"]["returns"], json!([]));
+    }
+}
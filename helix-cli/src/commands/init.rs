@@ -103,6 +103,7 @@ pub async fn run(
                     gateway_url: target.gateway_url,
                     query_auth_header: DEFAULT_QUERY_AUTH_HEADER.to_string(),
                     query_auth_env: DEFAULT_QUERY_AUTH_ENV.to_string(),
+                    query_auth_file: None,
                     availability_mode: None,
                     gateway_node_type: None,
                     db_node_type: None,
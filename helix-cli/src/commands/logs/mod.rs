@@ -29,12 +29,15 @@ pub async fn run(
     let instance = resolve_instance(&project, instance)?;
     match project.config.get_instance(&instance)? {
         InstanceInfo::Local(_) => {
-            if range || start.is_some() || end.is_some() {
+            if range {
                 return Err(eyre!(
-                    "--range, --start, and --end are only supported for Enterprise logs; local logs use docker/podman logs"
+                    "--range is only supported for Enterprise logs; use --since/--until to bound local logs"
                 ));
             }
-            LocalRuntime::new(&project).logs(&instance, follow)?;
+            let now = Utc::now();
+            let since = start.map(|start| parse_time_arg(&start, now)).transpose()?;
+            let until = end.map(|end| parse_time_arg(&end, now)).transpose()?;
+            LocalRuntime::new(&project).logs(&instance, follow, since, until)?;
         }
         InstanceInfo::Enterprise(config) => {
             if follow {
@@ -86,18 +89,60 @@ fn parse_range(
     start: Option<String>,
     end: Option<String>,
 ) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let now = Utc::now();
     let end = match end {
-        Some(end) => DateTime::parse_from_rfc3339(&end)?.with_timezone(&Utc),
-        None => Utc::now(),
+        Some(end) => parse_time_arg(&end, now)?,
+        None => now,
     };
     let start = match start {
-        Some(start) => DateTime::parse_from_rfc3339(&start)?.with_timezone(&Utc),
+        Some(start) => parse_time_arg(&start, now)?,
         None if range => end - Duration::hours(1),
         None => end - Duration::hours(1),
     };
+    if start >= end {
+        return Err(eyre!(
+            "Log range start ({start}) must be before end ({end})"
+        ));
+    }
     Ok((start, end))
 }
 
+/// Resolves a `--since`/`--until` value against `now`. Accepts an RFC 3339
+/// timestamp, `now`, or a relative duration such as `30m`, `2h`, `1d`,
+/// `2 days ago`.
+fn parse_time_arg(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let trimmed = input.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    if trimmed.eq_ignore_ascii_case("now") {
+        return Ok(now);
+    }
+    let duration = parse_relative_duration(trimmed).ok_or_else(|| {
+        eyre!(
+            "Invalid time '{input}': expected an RFC 3339 timestamp or a relative duration like 30m, 2h, 1d, or \"2 days ago\""
+        )
+    })?;
+    now.checked_sub_signed(duration)
+        .ok_or_else(|| eyre!("Invalid time '{input}': duration is too large"))
+}
+
+fn parse_relative_duration(input: &str) -> Option<Duration> {
+    let lower = input.to_ascii_lowercase();
+    let body = lower.strip_suffix("ago").unwrap_or(&lower).trim_end();
+    let split = body.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = body.split_at(split);
+    let amount: i64 = amount.parse().ok()?;
+    match unit.trim_start() {
+        "s" | "sec" | "secs" | "second" | "seconds" => Duration::try_seconds(amount),
+        "m" | "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(amount),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Duration::try_hours(amount),
+        "d" | "day" | "days" => Duration::try_days(amount),
+        "w" | "week" | "weeks" => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
 async fn query_enterprise_logs(
     cluster_id: &str,
     api_key: &str,
@@ -123,3 +168,53 @@ async fn query_enterprise_logs(
     let payload: LogsRangeResponse = response.json().await?;
     Ok(payload.logs.into_iter().map(|log| log.message).collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed_now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn relative_durations_resolve_against_now() {
+        let now = fixed_now();
+        assert_eq!(parse_time_arg("1h", now).unwrap(), now - Duration::hours(1));
+        assert_eq!(
+            parse_time_arg("30m", now).unwrap(),
+            now - Duration::minutes(30)
+        );
+        assert_eq!(parse_time_arg("1d", now).unwrap(), now - Duration::days(1));
+        assert_eq!(
+            parse_time_arg("2 days ago", now).unwrap(),
+            now - Duration::days(2)
+        );
+        assert_eq!(parse_time_arg("now", now).unwrap(), now);
+    }
+
+    #[test]
+    fn absolute_timestamps_still_parse() {
+        let parsed = parse_time_arg("2025-05-31T10:00:00+02:00", fixed_now()).unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2025-05-31T08:00:00+00:00");
+    }
+
+    #[test]
+    fn invalid_durations_error() {
+        for input in ["", "h", "1", "1 fortnight", "-1h", "yesterday"] {
+            let err = parse_time_arg(input, fixed_now()).unwrap_err();
+            assert!(err.to_string().contains("Invalid time"), "{input}: {err}");
+        }
+    }
+
+    #[test]
+    fn range_rejects_start_after_end() {
+        let err = parse_range(true, Some("1h".into()), Some("2h".into())).unwrap_err();
+        assert!(err.to_string().contains("must be before"));
+
+        let (start, end) = parse_range(true, Some("2h".into()), None).unwrap();
+        assert_eq!(end - start, Duration::hours(2));
+    }
+}
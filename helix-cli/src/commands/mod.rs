@@ -3,8 +3,10 @@ pub mod auth;
 pub mod chef;
 pub mod config;
 pub mod delete;
+pub mod doctor;
 pub mod enterprise_deploy;
 pub mod feedback;
+pub mod import_csv;
 pub mod init;
 pub mod logs;
 pub mod metrics;
@@ -1,16 +1,20 @@
-use crate::commands::enterprise_deploy::deploy_enterprise;
-use crate::config::InstanceInfo;
+use crate::commands::enterprise_deploy::{
+    EnterpriseDeployPlan, deploy_enterprise, prepare_enterprise_deploy,
+};
+use crate::config::{EnterpriseInstanceConfig, InstanceInfo};
 use crate::errors::CliError;
 use crate::metrics_sender::MetricsSender;
 use crate::output::Operation;
 use crate::project::ProjectContext;
 use crate::prompts;
 use eyre::{Result, eyre};
+use std::io::Write;
 use std::time::Instant;
 
 pub async fn run(
     instance_name: Option<String>,
     dev: bool,
+    dry_run: bool,
     metrics_sender: &MetricsSender,
 ) -> Result<()> {
     let start_time = Instant::now();
@@ -35,14 +39,45 @@ pub async fn run(
         ));
     };
 
-    let op = Operation::new("Deploying", &instance_name);
-    let deploy_result = deploy_enterprise(&project, &instance_name, config).await;
+    push_or_plan(
+        dry_run,
+        || prepare_enterprise_deploy(&project, &instance_name, config),
+        async || {
+            deploy_and_report(&project, &instance_name, config, start_time, metrics_sender).await
+        },
+        &mut std::io::stdout(),
+    )
+    .await
+}
+
+/// With `dry_run`, print the plan to `out` and return without calling `deploy`.
+async fn push_or_plan(
+    dry_run: bool,
+    prepare_plan: impl FnOnce() -> Result<EnterpriseDeployPlan>,
+    deploy: impl AsyncFnOnce() -> Result<()>,
+    out: &mut impl Write,
+) -> Result<()> {
+    if dry_run {
+        return print_deploy_plan(&prepare_plan()?, out);
+    }
+    deploy().await
+}
+
+async fn deploy_and_report(
+    project: &ProjectContext,
+    instance_name: &str,
+    config: &EnterpriseInstanceConfig,
+    start_time: Instant,
+    metrics_sender: &MetricsSender,
+) -> Result<()> {
+    let op = Operation::new("Deploying", instance_name);
+    let deploy_result = deploy_enterprise(project, instance_name, config).await;
     let duration = start_time.elapsed().as_secs() as u32;
     let success = deploy_result.is_ok();
     let error_messages = deploy_result.as_ref().err().map(|error| error.to_string());
 
     metrics_sender.send_deploy_cloud_event(
-        instance_name.clone(),
+        instance_name.to_string(),
         String::new(),
         0,
         duration,
@@ -69,6 +104,17 @@ pub async fn run(
     }
 }
 
+/// Print what a deploy would send, as built by the local steps, without
+/// contacting Helix Cloud. Used by `--dry-run`.
+fn print_deploy_plan(plan: &EnterpriseDeployPlan, out: &mut impl Write) -> Result<()> {
+    crate::output::info("Planned actions:");
+    for line in plan.summary_lines() {
+        writeln!(out, "  {line}")?;
+    }
+    crate::output::info("Dry run: nothing was deployed.");
+    Ok(())
+}
+
 fn resolve_instance_name(
     instance_name: Option<String>,
     project: &ProjectContext,
@@ -104,3 +150,68 @@ fn resolve_instance_name(
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan() -> EnterpriseDeployPlan {
+        EnterpriseDeployPlan {
+            instance_name: "production".to_string(),
+            cluster_id: "cluster-123".to_string(),
+            deploy_url: "https://cloud.example/api/cli/enterprise-clusters/cluster-123/deploy"
+                .to_string(),
+            queries_json_size_bytes: 14,
+            source_files: vec!["Cargo.toml".to_string()],
+            helix_toml: None,
+            payload: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn dry_run_prints_plan_without_deploying() {
+        let mut out = Vec::new();
+        let mut deployed = false;
+
+        push_or_plan(
+            true,
+            || Ok(plan()),
+            async || {
+                deployed = true;
+                Ok(())
+            },
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        assert!(!deployed);
+        let expected: String = plan()
+            .summary_lines()
+            .iter()
+            .map(|line| format!("  {line}\n"))
+            .collect();
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn push_deploys_without_building_a_plan() {
+        let mut out = Vec::new();
+        let mut deployed = false;
+
+        push_or_plan(
+            false,
+            || panic!("plan built for a real deploy"),
+            async || {
+                deployed = true;
+                Ok(())
+            },
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        assert!(deployed);
+        assert!(out.is_empty());
+    }
+}
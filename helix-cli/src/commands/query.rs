@@ -1,9 +1,10 @@
-use crate::config::InstanceInfo;
+use crate::config::{EnterpriseInstanceConfig, InstanceInfo};
 use crate::errors::CliError;
 use crate::project::ProjectContext;
 use eyre::{Report, Result, eyre};
 use reqwest::header::{CONTENT_TYPE, HeaderName, HeaderValue};
 use serde_json::Value;
+use std::path::{Path, PathBuf};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn run(
@@ -12,6 +13,7 @@ pub async fn run(
     json: Option<String>,
     ts: Option<String>,
     ts_file: Option<String>,
+    params: Option<String>,
     warm: bool,
     host: Option<String>,
     port: Option<u16>,
@@ -22,11 +24,40 @@ pub async fn run(
     // instead of requiring the caller to export it in their shell.
     let _ = dotenvy::from_path(project.root.join(".env"));
     let instance = instance.unwrap_or_else(|| "dev".to_string());
-    let request_json = parse_query_request(file, json, ts, ts_file)?;
+    let mut request_json = parse_query_request(file, json, ts, ts_file)?;
+    if let Some(source) = params {
+        let params = read_query_params(&source, std::io::stdin().lock())?;
+        apply_query_params(&mut request_json, params)?;
+    }
 
     validate_dynamic_request(&request_json, warm)?;
+    let Some(value) = send_query(&project, &instance, &request_json, warm, host, port).await?
+    else {
+        return Ok(());
+    };
+    if crate::output::Verbosity::current().show_normal() {
+        if compact {
+            println!("{}", serde_json::to_string(&value)?);
+        } else {
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        }
+    }
+    Ok(())
+}
+
+/// POST a dynamic query request to an instance's `/v1/query` endpoint, with the
+/// instance's auth header for Enterprise. Returns the parsed response body, or
+/// `None` when the instance replied with no content.
+pub(crate) async fn send_query(
+    project: &ProjectContext,
+    instance: &str,
+    request_json: &Value,
+    warm: bool,
+    host: Option<String>,
+    port: Option<u16>,
+) -> Result<Option<Value>> {
     let client = reqwest::Client::new();
-    let (mut request, endpoint, is_local) = match project.config.get_instance(&instance)? {
+    let (mut request, endpoint, is_local) = match project.config.get_instance(instance)? {
         InstanceInfo::Local(config) => {
             let host = host.unwrap_or_else(|| "localhost".to_string());
             let port = port.unwrap_or(config.port);
@@ -39,17 +70,8 @@ pub async fn run(
                     "Enterprise gateway URL is not configured for '{instance}'. Run 'helix sync {instance}' or set gateway_url in helix.toml."
                 )
             })?;
-            let auth_value = std::env::var(&config.query_auth_env).map_err(|_| -> Report {
-                CliError::new(format!(
-                    "environment variable {} is required for Enterprise query auth",
-                    config.query_auth_env
-                ))
-                .with_hint(format!(
-                    "set {} in a .env file in your project root, or export it in your shell",
-                    config.query_auth_env
-                ))
-                .into()
-            })?;
+            let auth_value =
+                resolve_query_auth(config, &project.root, |name| std::env::var(name).ok())?;
             let header_name = HeaderName::from_bytes(config.query_auth_header.as_bytes())?;
             let endpoint = format!("{}/v1/query", gateway_url.trim_end_matches('/'));
             (
@@ -68,19 +90,19 @@ pub async fn run(
     }
 
     let response = request
-        .json(&request_json)
+        .json(request_json)
         .send()
         .await
         .map_err(|e| -> Report {
             if e.is_connect() || e.is_timeout() {
-                connect_error(&instance, &endpoint, is_local, &e.to_string()).into()
+                connect_error(instance, &endpoint, is_local, &e.to_string()).into()
             } else {
                 e.into()
             }
         })?;
     let status = response.status();
     if status == reqwest::StatusCode::NO_CONTENT {
-        return Ok(());
+        return Ok(None);
     }
     let body = response.text().await.unwrap_or_default();
     if !status.is_success() {
@@ -88,17 +110,63 @@ pub async fn run(
     }
 
     if body.trim().is_empty() {
-        return Ok(());
+        return Ok(None);
     }
-    let value: Value = serde_json::from_str(&body).unwrap_or(Value::String(body));
-    if crate::output::Verbosity::current().show_normal() {
-        if compact {
-            println!("{}", serde_json::to_string(&value)?);
-        } else {
-            println!("{}", serde_json::to_string_pretty(&value)?);
-        }
+    Ok(Some(
+        serde_json::from_str(&body).unwrap_or(Value::String(body)),
+    ))
+}
+
+/// The Enterprise query API key, from the first of: the `query_auth_env` variable,
+/// a file named by `<query_auth_env>_FILE`, or the instance's `query_auth_file`.
+/// Errors name the variable or file but never include the key itself.
+pub(crate) fn resolve_query_auth(
+    config: &EnterpriseInstanceConfig,
+    root: &Path,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<String> {
+    let env_name = &config.query_auth_env;
+    if let Some(key) = lookup(env_name).filter(|key| !key.is_empty()) {
+        return Ok(key);
     }
-    Ok(())
+
+    let file_env = format!("{env_name}_FILE");
+    let (path, source) = match lookup(&file_env).filter(|path| !path.is_empty()) {
+        Some(path) => (PathBuf::from(path), file_env),
+        None => match &config.query_auth_file {
+            Some(path) => (root.join(path), "query_auth_file in helix.toml".to_string()),
+            None => {
+                return Err(CliError::new(format!(
+                    "environment variable {env_name} is required for Enterprise query auth"
+                ))
+                .with_hint(format!(
+                    "set {env_name} in a .env file in your project root, export it in your shell, \
+                     or point {env_name}_FILE or `query_auth_file` at a file containing the key"
+                ))
+                .into());
+            }
+        },
+    };
+
+    let key = std::fs::read_to_string(&path).map_err(|e| -> Report {
+        CliError::new(format!(
+            "failed to read the Enterprise query API key from {}",
+            path.display()
+        ))
+        .with_context(format!("path set by {source}"))
+        .with_caused_by(e.to_string())
+        .into()
+    })?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(CliError::new(format!(
+            "the Enterprise query API key file {} is empty",
+            path.display()
+        ))
+        .with_context(format!("path set by {source}"))
+        .into());
+    }
+    Ok(key.to_string())
 }
 
 /// Error for a query that never reached a Helix instance (connection refused,
@@ -169,6 +237,42 @@ fn parse_query_request(
     crate::ts_query::build_request_from_ts(&snippet)
 }
 
+/// Read the `--params` value: `-` reads stdin, `@path` reads a file, and anything
+/// else is parsed as inline JSON. The result must be a JSON object.
+fn read_query_params(source: &str, mut stdin: impl std::io::Read) -> Result<Value> {
+    let (origin, text) = if source == "-" {
+        let mut text = String::new();
+        stdin
+            .read_to_string(&mut text)
+            .map_err(|e| eyre!("Failed to read query parameters from stdin: {e}"))?;
+        ("stdin".to_string(), text)
+    } else if let Some(path) = source.strip_prefix('@') {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read query parameters file '{path}': {e}"))?;
+        (format!("'{path}'"), text)
+    } else {
+        ("--params".to_string(), source.to_string())
+    };
+
+    let params: Value = serde_json::from_str(&text)
+        .map_err(|e| eyre!("Failed to parse query parameters from {origin}: {e}"))?;
+    if !params.is_object() {
+        return Err(eyre!(
+            "Query parameters from {origin} must be a JSON object"
+        ));
+    }
+    Ok(params)
+}
+
+/// Set the request's `parameters`, replacing any already in the request body.
+fn apply_query_params(request: &mut Value, params: Value) -> Result<()> {
+    let request = request
+        .as_object_mut()
+        .ok_or_else(|| eyre!("dynamic query request must be a JSON object"))?;
+    request.insert("parameters".to_string(), params);
+    Ok(())
+}
+
 fn validate_dynamic_request(request: &Value, warm: bool) -> Result<()> {
     let request_type = request
         .get("request_type")
@@ -190,6 +294,55 @@ fn validate_dynamic_request(request: &Value, warm: bool) -> Result<()> {
 mod tests {
     use super::*;
 
+    fn enterprise_config(extra: &str) -> EnterpriseInstanceConfig {
+        toml::from_str(&format!("cluster_id = \"cluster-1\"\n{extra}")).unwrap()
+    }
+
+    #[test]
+    fn query_auth_prefers_env_then_file_env_then_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("env-file.key"), "from-env-file\n").unwrap();
+        std::fs::write(dir.path().join("config.key"), "  from-config-file \n").unwrap();
+        let env_file = dir.path().join("env-file.key").display().to_string();
+        let config = enterprise_config("query_auth_file = \"config.key\"");
+
+        let key = resolve_query_auth(&config, dir.path(), |name| match name {
+            "HELIX_API_KEY" => Some("from-env".to_string()),
+            "HELIX_API_KEY_FILE" => Some(env_file.clone()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(key, "from-env");
+
+        let key = resolve_query_auth(&config, dir.path(), |name| {
+            (name == "HELIX_API_KEY_FILE").then(|| env_file.clone())
+        })
+        .unwrap();
+        assert_eq!(key, "from-env-file");
+
+        let key = resolve_query_auth(&config, dir.path(), |_| None).unwrap();
+        assert_eq!(key, "from-config-file");
+    }
+
+    #[test]
+    fn query_auth_reports_missing_key_file_without_leaking_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = enterprise_config("query_auth_file = \"missing.key\"");
+
+        let err = resolve_query_auth(&config, dir.path(), |_| None).unwrap_err();
+        let rendered = err.to_string();
+        assert!(rendered.contains("failed to read the Enterprise query API key"));
+        assert!(rendered.contains("missing.key"));
+
+        std::fs::write(dir.path().join("empty.key"), "\n").unwrap();
+        let config = enterprise_config("query_auth_file = \"empty.key\"");
+        let err = resolve_query_auth(&config, dir.path(), |_| None).unwrap_err();
+        assert!(err.to_string().contains("is empty"));
+
+        let err = resolve_query_auth(&enterprise_config(""), dir.path(), |_| None).unwrap_err();
+        assert!(err.to_string().contains("HELIX_API_KEY is required"));
+    }
+
     #[test]
     fn parse_query_request_accepts_inline_json() {
         let request = parse_query_request(
@@ -203,6 +356,53 @@ mod tests {
         assert_eq!(request["request_type"], "read");
     }
 
+    #[test]
+    fn query_params_from_stdin_file_and_inline_build_the_same_request() {
+        let params = r#"{"name":"Ada","limit":10}"#;
+        let dir = tempfile::tempdir().unwrap();
+        let params_path = dir.path().join("params.json");
+        std::fs::write(&params_path, params).unwrap();
+        let base = serde_json::json!({"request_type": "read", "query": {"queries": []}});
+
+        let sources = [
+            ("-".to_string(), params.as_bytes()),
+            (format!("@{}", params_path.display()), &b""[..]),
+            (params.to_string(), &b""[..]),
+        ];
+        let requests: Vec<Value> = sources
+            .into_iter()
+            .map(|(source, stdin)| {
+                let mut request = base.clone();
+                let params = read_query_params(&source, stdin).unwrap();
+                apply_query_params(&mut request, params).unwrap();
+                request
+            })
+            .collect();
+
+        assert_eq!(
+            requests[0],
+            serde_json::json!({
+                "request_type": "read",
+                "query": {"queries": []},
+                "parameters": {"name": "Ada", "limit": 10}
+            })
+        );
+        assert_eq!(requests[0], requests[1]);
+        assert_eq!(requests[0], requests[2]);
+    }
+
+    #[test]
+    fn query_params_must_be_a_json_object() {
+        let err = read_query_params("-", &b"{not json"[..]).unwrap_err();
+        assert!(err.to_string().contains("from stdin"), "{err}");
+
+        let err = read_query_params("[1, 2]", &b""[..]).unwrap_err();
+        assert!(err.to_string().contains("must be a JSON object"), "{err}");
+
+        let err = read_query_params("@/nonexistent/params.json", &b""[..]).unwrap_err();
+        assert!(err.to_string().contains("Failed to read"), "{err}");
+    }
+
     #[test]
     fn parse_query_request_rejects_missing_input() {
         let error = parse_query_request(None, None, None, None)
@@ -1,9 +1,28 @@
-use crate::config::InstanceInfo;
-use crate::local_runtime::LocalRuntime;
+use crate::commands::auth::stored_credentials;
+use crate::config::{EnterpriseInstanceConfig, InstanceInfo, LocalInstanceConfig};
+use crate::enterprise_cloud::{CliEnterpriseCluster, cloud_base_url, resolve_enterprise_cluster};
+use crate::local_runtime::{LocalResourceUsage, LocalRuntime};
 use crate::project::ProjectContext;
 use crate::prompts::{self, StatusSelection};
 use crate::utils::{print_field, print_header, print_newline};
 use eyre::Result;
+use futures_util::future::join_all;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Everything shown for a single instance row in `helix status`.
+enum InstanceStatus<'a> {
+    Local {
+        config: &'a LocalInstanceConfig,
+        state: String,
+        data_size: Option<String>,
+        usage: Option<LocalResourceUsage>,
+    },
+    Enterprise {
+        config: &'a EnterpriseInstanceConfig,
+        cluster: Option<&'a CliEnterpriseCluster>,
+    },
+}
 
 pub async fn run(instance: Option<String>) -> Result<()> {
     let project = ProjectContext::find_and_load(None)?;
@@ -13,15 +32,35 @@ pub async fn run(instance: Option<String>) -> Result<()> {
     print_field("Root", &project.root.display().to_string());
     print_newline();
 
+    let names = match resolve_status_selection(&project, instance)? {
+        StatusSelection::All => project
+            .config
+            .list_instances()
+            .into_iter()
+            .cloned()
+            .collect(),
+        StatusSelection::Instance(instance) => vec![instance],
+    };
+
     let runtime = LocalRuntime::new(&project);
+    let clusters = fetch_cloud_clusters(&project, &names).await;
+    // Memory storage keeps nothing on disk; disk storage lives in a MinIO data
+    // volume, and one runtime report covers every instance's volume.
+    let has_disk_instance = names.iter().any(|name| {
+        project
+            .config
+            .local
+            .get(name)
+            .is_some_and(|config| config.storage.is_disk())
+    });
+    let disk_usage = if has_disk_instance {
+        runtime.disk_usage_report()
+    } else {
+        None
+    };
     print_header("Instances");
-    match resolve_status_selection(&project, instance)? {
-        StatusSelection::All => {
-            for name in project.config.list_instances() {
-                print_instance(&project, &runtime, name)?;
-            }
-        }
-        StatusSelection::Instance(instance) => print_instance(&project, &runtime, &instance)?,
+    for name in &names {
+        print_instance(&project, &runtime, &clusters, disk_usage.as_deref(), name)?;
     }
 
     Ok(())
@@ -41,35 +80,149 @@ fn resolve_status_selection(
     Ok(StatusSelection::All)
 }
 
-fn print_instance(project: &ProjectContext, runtime: &LocalRuntime, name: &str) -> Result<()> {
-    match project.config.get_instance(name)? {
+/// Upper bound on each Cloud request made by `helix status`, so a slow or
+/// unreachable API delays the report by seconds rather than hanging it.
+const CLOUD_STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Best-effort lookup of Cloud metadata (cluster name, region, deployment state,
+/// availability mode) for the selected Enterprise instances, keyed by cluster ID.
+/// Status must keep working offline and logged out, so any failure just leaves
+/// the cluster out. Fields the API doesn't return are left off the row.
+async fn fetch_cloud_clusters(
+    project: &ProjectContext,
+    names: &[String],
+) -> HashMap<String, CliEnterpriseCluster> {
+    let mut clusters = HashMap::new();
+    let enterprise: Vec<&EnterpriseInstanceConfig> = names
+        .iter()
+        .filter_map(|name| project.config.enterprise.get(name))
+        .collect();
+    if enterprise.is_empty() {
+        return clusters;
+    }
+    let Some(credentials) = stored_credentials() else {
+        return clusters;
+    };
+
+    let Ok(client) = reqwest::Client::builder()
+        .timeout(CLOUD_STATUS_TIMEOUT)
+        .build()
+    else {
+        return clusters;
+    };
+    let base_url = cloud_base_url();
+    let lookups = enterprise.into_iter().map(|config| {
+        let (client, base_url) = (&client, &base_url);
+        let api_key = &credentials.helix_admin_key;
+        async move {
+            let result = resolve_enterprise_cluster(
+                client,
+                base_url,
+                api_key,
+                &config.cluster_id,
+                config.project_id.as_deref(),
+                config.workspace_id.as_deref(),
+            )
+            .await;
+            (config, result)
+        }
+    });
+    for (config, result) in join_all(lookups).await {
+        match result {
+            Ok(resolved) => {
+                clusters.insert(config.cluster_id.clone(), resolved.cluster);
+            }
+            Err(error) => crate::output::verbose(&format!(
+                "Could not fetch Cloud status for cluster {}: {error}",
+                config.cluster_id
+            )),
+        }
+    }
+    clusters
+}
+
+fn print_instance(
+    project: &ProjectContext,
+    runtime: &LocalRuntime,
+    clusters: &HashMap<String, CliEnterpriseCluster>,
+    disk_usage: Option<&str>,
+    name: &str,
+) -> Result<()> {
+    let status = match project.config.get_instance(name)? {
         InstanceInfo::Local(config) => {
             let status = runtime.status(name)?;
             let state = status
                 .as_ref()
-                .map(|status| status.status.as_str())
-                .unwrap_or("not created");
-            print_field(
-                &format!("{name} (local)"),
-                &format!(
-                    "http://localhost:{} - {state} - storage: {}",
-                    config.port,
-                    config.storage.as_str()
-                ),
+                .map(|status| status.status.clone())
+                .unwrap_or_else(|| "not created".to_string());
+            let usage = if status.is_some() {
+                runtime.resource_usage(name).unwrap_or(None)
+            } else {
+                None
+            };
+            let data_size = disk_usage
+                .filter(|_| config.storage.is_disk())
+                .and_then(|report| runtime.data_volume_size(report, name));
+            InstanceStatus::Local {
+                config,
+                state,
+                data_size,
+                usage,
+            }
+        }
+        InstanceInfo::Enterprise(config) => InstanceStatus::Enterprise {
+            config,
+            cluster: clusters.get(&config.cluster_id),
+        },
+    };
+
+    let (label, details) = status_row(name, &status);
+    print_field(&label, &details);
+    Ok(())
+}
+
+fn status_row(name: &str, status: &InstanceStatus<'_>) -> (String, String) {
+    match status {
+        InstanceStatus::Local {
+            config,
+            state,
+            data_size,
+            usage,
+        } => {
+            let mut details = format!(
+                "http://localhost:{} - {state} - storage: {}",
+                config.port,
+                config.storage.as_str()
             );
+            if let Some(size) = data_size {
+                details.push_str(&format!(" - data: {size}"));
+            }
+            if let Some(usage) = usage {
+                details.push_str(&format!(" - cpu: {} - mem: {}", usage.cpu, usage.memory));
+            }
+            (format!("{name} (local)"), details)
         }
-        InstanceInfo::Enterprise(config) => {
+        InstanceStatus::Enterprise { config, cluster } => {
             let gateway = config
                 .gateway_url
                 .as_deref()
                 .unwrap_or("gateway not configured");
-            print_field(
-                &format!("{name} (Enterprise)"),
-                &format!("cluster {} - {gateway}", config.cluster_id),
-            );
+            let mut details = format!("cluster {} - {gateway}", config.cluster_id);
+            if let Some(cluster) = cluster {
+                details.push_str(&format!(" - name: {}", cluster.name));
+                if let Some(region) = &cluster.region {
+                    details.push_str(&format!(" - region: {region}"));
+                }
+                if let Some(state) = &cluster.status {
+                    details.push_str(&format!(" - {state}"));
+                }
+                if let Some(mode) = &cluster.availability_mode {
+                    details.push_str(&format!(" - availability: {mode}"));
+                }
+            }
+            (format!("{name} (Enterprise)"), details)
         }
     }
-    Ok(())
 }
 
 fn all_instances(project: &ProjectContext) -> Vec<(String, String)> {
@@ -80,3 +233,74 @@ fn all_instances(project: &ProjectContext) -> Vec<(String, String)> {
         .map(|(name, kind)| (name.clone(), kind.to_string()))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HelixConfig;
+
+    #[test]
+    fn mixed_local_and_cloud_rows_render_resources_and_cloud_details() {
+        let config: HelixConfig = toml::from_str(
+            r#"
+[project]
+name = "demo"
+
+[local.dev]
+port = 6970
+storage = "disk"
+
+[enterprise.production]
+cluster_id = "cluster-123"
+gateway_url = "https://gw.example.com"
+"#,
+        )
+        .unwrap();
+        let cluster: CliEnterpriseCluster = serde_json::from_value(serde_json::json!({
+            "cluster_id": "cluster-123",
+            "cluster_name": "prod",
+            "availability_mode": "ha",
+            "region": "us-east-1",
+            "deployment_status": "running"
+        }))
+        .unwrap();
+
+        let local = InstanceStatus::Local {
+            config: config.local.get("dev").unwrap(),
+            state: "Up 3 minutes".to_string(),
+            data_size: Some("2.0kB".to_string()),
+            usage: Some(LocalResourceUsage {
+                cpu: "1.25%".to_string(),
+                memory: "40MiB / 2GiB".to_string(),
+            }),
+        };
+        let cloud = InstanceStatus::Enterprise {
+            config: config.enterprise.get("production").unwrap(),
+            cluster: Some(&cluster),
+        };
+        let offline_cloud = InstanceStatus::Enterprise {
+            config: config.enterprise.get("production").unwrap(),
+            cluster: None,
+        };
+
+        assert_eq!(
+            status_row("dev", &local),
+            (
+                "dev (local)".to_string(),
+                "http://localhost:6970 - Up 3 minutes - storage: disk - data: 2.0kB - cpu: 1.25% - mem: 40MiB / 2GiB".to_string()
+            )
+        );
+        assert_eq!(
+            status_row("production", &cloud),
+            (
+                "production (Enterprise)".to_string(),
+                "cluster cluster-123 - https://gw.example.com - name: prod - region: us-east-1 - running - availability: ha"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            status_row("production", &offline_cloud).1,
+            "cluster cluster-123 - https://gw.example.com"
+        );
+    }
+}
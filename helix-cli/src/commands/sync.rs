@@ -419,13 +419,13 @@ async fn reconcile_enterprise_cluster_snapshot(
                 ));
             }
 
+            let diff = compute_manifest_diff(&local_manifest, &remote_manifest);
+            print_plan_for_direction(&diff, SyncDirection::Push);
             match confirm_sync_action(
                 assume_yes,
                 "your enterprise cluster has no source snapshot. Push your local query project to cloud now?",
             )? {
                 true => {
-                    let diff = compute_manifest_diff(&local_manifest, &remote_manifest);
-                    print_plan_for_direction(&diff, SyncDirection::Push);
                     push_local_enterprise_snapshot_to_cluster(project, cluster_id, cluster_name)
                         .await?;
                     outcome = SyncReconciliationOutcome::Pushed;
@@ -434,13 +434,13 @@ async fn reconcile_enterprise_cluster_snapshot(
             }
         }
         SnapshotComparison::RemoteOnly => {
+            let diff = compute_manifest_diff(&local_manifest, &remote_manifest);
+            print_plan_for_direction(&diff, SyncDirection::Pull);
             match confirm_sync_action(
                 assume_yes,
                 "Local enterprise source is empty while cloud has files. Pull cloud files to local?",
             )? {
                 true => {
-                    let diff = compute_manifest_diff(&local_manifest, &remote_manifest);
-                    print_plan_for_direction(&diff, SyncDirection::Pull);
                     apply_pull()?;
                     outcome = SyncReconciliationOutcome::Pulled;
                 }
@@ -461,11 +461,11 @@ async fn reconcile_enterprise_cluster_snapshot(
                 };
 
                 if push_allowed {
+                    print_plan_for_direction(&diff, SyncDirection::Push);
                     if confirm_sync_action(
                         assume_yes,
                         "Local enterprise changes are newer. Push your local query project to cloud?",
                     )? {
-                        print_plan_for_direction(&diff, SyncDirection::Push);
                         push_local_enterprise_snapshot_to_cluster(
                             project,
                             cluster_id,
@@ -473,11 +473,7 @@ async fn reconcile_enterprise_cluster_snapshot(
                         )
                         .await?;
                         outcome = SyncReconciliationOutcome::Pushed;
-                    } else if confirm_sync_action(
-                        false,
-                        "Overwrite local enterprise files with cloud changes instead?",
-                    )? {
-                        print_plan_for_direction(&diff, SyncDirection::Pull);
+                    } else if confirm_overwrite_local(&diff)? {
                         apply_pull()?;
                         outcome = SyncReconciliationOutcome::Pulled;
                     } else {
@@ -488,11 +484,7 @@ async fn reconcile_enterprise_cluster_snapshot(
                         "Local push skipped because enterprise query project failed validation.",
                     );
                     crate::output::info("Left local and cloud changes unchanged.");
-                } else if confirm_sync_action(
-                    false,
-                    "Overwrite local enterprise files with cloud changes instead?",
-                )? {
-                    print_plan_for_direction(&diff, SyncDirection::Pull);
+                } else if confirm_overwrite_local(&diff)? {
                     apply_pull()?;
                     outcome = SyncReconciliationOutcome::Pulled;
                 } else {
@@ -500,12 +492,12 @@ async fn reconcile_enterprise_cluster_snapshot(
                 }
             }
             DivergenceAuthority::RemoteNewer => {
+                print_plan_for_direction(&diff, SyncDirection::Pull);
                 match confirm_sync_action(
                     assume_yes,
                     "Enterprise cloud changes are newer. Pull cloud files to local?",
                 )? {
                     true => {
-                        print_plan_for_direction(&diff, SyncDirection::Pull);
                         apply_pull()?;
                         outcome = SyncReconciliationOutcome::Pulled;
                     }
@@ -524,17 +516,23 @@ async fn reconcile_enterprise_cluster_snapshot(
                     }
                 };
 
+                if !assume_yes && prompts::is_interactive() {
+                    crate::output::info("If you pull:");
+                    print_plan_for_direction(&diff, SyncDirection::Pull);
+                    if allow_push {
+                        crate::output::info("If you push:");
+                        print_plan_for_direction(&diff, SyncDirection::Push);
+                    }
+                }
                 match resolve_tie_action(assume_yes, allow_push)? {
                     TieResolutionAction::NoOp => {
                         crate::output::info("Left local and cloud changes unchanged.");
                     }
                     TieResolutionAction::Pull => {
-                        print_plan_for_direction(&diff, SyncDirection::Pull);
                         apply_pull()?;
                         outcome = SyncReconciliationOutcome::Pulled;
                     }
                     TieResolutionAction::Push => {
-                        print_plan_for_direction(&diff, SyncDirection::Push);
                         push_local_enterprise_snapshot_to_cluster(
                             project,
                             cluster_id,
@@ -917,6 +915,17 @@ fn confirm_sync_action(assume_yes: bool, prompt: &str) -> Result<bool> {
     prompts::confirm(prompt)
 }
 
+/// Ask whether to overwrite local files with the cloud snapshot after a push was
+/// declined. Lists the local files the pull would change or delete first, so local
+/// edits are never replaced without the user seeing which ones.
+fn confirm_overwrite_local(diff: &ManifestDiff) -> Result<bool> {
+    print_plan_for_direction(diff, SyncDirection::Pull);
+    confirm_sync_action(
+        false,
+        "Overwrite local enterprise files with cloud changes instead?",
+    )
+}
+
 fn resolve_tie_action(assume_yes: bool, allow_push: bool) -> Result<TieResolutionAction> {
     if assume_yes || !prompts::is_interactive() {
         crate::output::warning(
@@ -1093,6 +1102,48 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn manifest_diff_detects_changed_content_and_one_sided_files() {
+        let mut local = HashMap::new();
+        local.insert(
+            "src/main.rs".to_string(),
+            manifest_entry(&compute_sha256("fn main() { local_edit(); }\n"), None),
+        );
+        local.insert(
+            "Cargo.toml".to_string(),
+            manifest_entry(&compute_sha256("[package]\n"), None),
+        );
+        local.insert(
+            "src/scratch.rs".to_string(),
+            manifest_entry(&compute_sha256("// wip\n"), None),
+        );
+
+        let mut remote = HashMap::new();
+        remote.insert(
+            "src/main.rs".to_string(),
+            manifest_entry(&compute_sha256("fn main() {}\n"), None),
+        );
+        remote.insert(
+            "Cargo.toml".to_string(),
+            manifest_entry(&compute_sha256("[package]\n"), None),
+        );
+        remote.insert(
+            "src/queries.rs".to_string(),
+            manifest_entry(&compute_sha256("// queries\n"), None),
+        );
+
+        let diff = compute_manifest_diff(&local, &remote);
+        assert_eq!(diff.changed, vec!["src/main.rs".to_string()]);
+        assert_eq!(diff.local_only, vec!["src/scratch.rs".to_string()]);
+        assert_eq!(diff.remote_only, vec!["src/queries.rs".to_string()]);
+
+        // A pull overwrites the locally edited file and deletes the local-only one.
+        let plan = build_sync_action_plan(&diff, SyncDirection::Pull);
+        assert_eq!(plan.to_change, vec!["src/main.rs".to_string()]);
+        assert_eq!(plan.to_delete, vec!["src/scratch.rs".to_string()]);
+        assert_eq!(plan.to_create, vec!["src/queries.rs".to_string()]);
+    }
+
     #[test]
     fn build_remote_enterprise_manifest_normalizes_paths_and_uses_metadata() {
         let mut source_files = HashMap::new();
@@ -61,6 +61,18 @@ pub struct HelixConfig {
     pub local: HashMap<String, LocalInstanceConfig>,
     #[serde(default)]
     pub enterprise: HashMap<String, EnterpriseInstanceConfig>,
+    /// `${VAR}` references expanded while loading, so saving writes the
+    /// references back instead of the resolved (possibly secret) values.
+    #[serde(skip)]
+    pub(crate) env_templates: Vec<EnvTemplate>,
+}
+
+/// A string value in `helix.toml` that contained `${VAR}` references.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EnvTemplate {
+    path: Vec<String>,
+    template: String,
+    resolved: toml::Value,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,6 +130,10 @@ pub struct LocalInstanceConfig {
     pub tag: String,
     #[serde(default, skip_serializing_if = "is_default_local_storage")]
     pub storage: LocalStorageMode,
+    /// Server log verbosity, passed to the container as `RUST_LOG` by `helix start`
+    /// and `helix restart`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<LogLevel>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
@@ -145,6 +161,28 @@ impl LocalStorageMode {
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct VectorConfig {
     #[serde(default = "default_m")]
@@ -272,14 +310,111 @@ impl Default for LocalInstanceConfig {
             image: DEFAULT_ENTERPRISE_DEV_IMAGE.to_string(),
             tag: DEFAULT_ENTERPRISE_DEV_TAG.to_string(),
             storage: LocalStorageMode::Memory,
+            log_level: None,
         }
     }
 }
 
 impl LocalInstanceConfig {
     pub fn image_ref(&self) -> String {
-        format!("{}:{}", self.image, self.tag)
+        match self.digest() {
+            Some(digest) => format!("{}@{digest}", self.image),
+            None => format!("{}:{}", self.image, self.tag),
+        }
+    }
+
+    /// The digest when `tag` pins one: `sha256:<hex>`, also accepted as `@sha256:<hex>`.
+    fn digest(&self) -> Option<&str> {
+        let digest = self.tag.strip_prefix('@').unwrap_or(&self.tag);
+        digest.starts_with("sha256:").then_some(digest)
+    }
+
+    /// Check that `image` and `tag` combine into a valid container image reference.
+    /// `image` is `[registry[:port]/]path` without a tag or digest, which go in `tag`.
+    /// A digest in `tag` pins the image as `image@sha256:<hex>`.
+    fn validate_image_ref(&self) -> Result<(), String> {
+        let mut components: Vec<&str> = self.image.split('/').collect();
+        if components.len() > 1 {
+            let first = components[0];
+            if first.contains(['.', ':']) || first == "localhost" {
+                validate_registry_host(first)?;
+                components.remove(0);
+            }
+        }
+        for component in components {
+            validate_path_component(component)?;
+        }
+
+        if let Some(digest) = self.digest() {
+            let hex = &digest["sha256:".len()..];
+            let valid_digest =
+                hex.len() == 64 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'));
+            if !valid_digest {
+                return Err(format!(
+                    "digest '{digest}' must be 'sha256:' followed by 64 lowercase hex characters"
+                ));
+            }
+            return Ok(());
+        }
+
+        let tag = &self.tag;
+        let valid_tag = tag.len() <= 128
+            && tag
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+            && tag
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+        if !valid_tag {
+            return Err(format!(
+                "tag '{tag}' must be 1-128 letters, digits, '_', '.' or '-' and not start with '.' or '-'"
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn validate_registry_host(host: &str) -> Result<(), String> {
+    let (name, port) = match host.split_once(':') {
+        Some((name, port)) => (name, Some(port)),
+        None => (host, None),
+    };
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-'));
+    let valid_port =
+        port.is_none_or(|port| !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()));
+    if !valid_name || !valid_port {
+        return Err(format!("registry '{host}' is not a valid host[:port]"));
     }
+    Ok(())
+}
+
+fn validate_path_component(component: &str) -> Result<(), String> {
+    if component.contains([':', '@']) {
+        return Err("put the tag in the `tag` field rather than in `image`".to_string());
+    }
+    let starts_and_ends_alphanumeric = component
+        .chars()
+        .next()
+        .zip(component.chars().last())
+        .is_some_and(|(first, last)| is_lower_alphanumeric(first) && is_lower_alphanumeric(last));
+    let valid = starts_and_ends_alphanumeric
+        && component
+            .chars()
+            .all(|c| is_lower_alphanumeric(c) || matches!(c, '.' | '_' | '-'));
+    if !valid {
+        return Err(format!(
+            "repository component '{component}' must be lowercase letters, digits, '.', '_' or '-'"
+        ));
+    }
+    Ok(())
+}
+
+fn is_lower_alphanumeric(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -295,6 +430,10 @@ pub struct EnterpriseInstanceConfig {
     pub query_auth_header: String,
     #[serde(default = "default_query_auth_env")]
     pub query_auth_env: String,
+    /// File holding the query API key, relative to the project root. Used when
+    /// neither `query_auth_env` nor `<query_auth_env>_FILE` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_auth_file: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub availability_mode: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -342,34 +481,107 @@ impl InstanceInfo<'_> {
 
 impl HelixConfig {
     pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
-        Self::from_file_inner(path, true)
+        Self::from_file_inner(path, true, |name| std::env::var(name).ok())
     }
 
     /// Like [`from_file`](Self::from_file), but tolerates a `helix.toml` that defines zero
     /// instances. Used by `helix add`, whose whole job is to add the first instance back —
     /// it would otherwise be locked out by the "at least one instance" check.
     pub fn from_file_allow_no_instances(path: &Path) -> Result<Self, ConfigError> {
-        Self::from_file_inner(path, false)
+        Self::from_file_inner(path, false, |name| std::env::var(name).ok())
     }
 
-    fn from_file_inner(path: &Path, require_instances: bool) -> Result<Self, ConfigError> {
+    /// Like [`from_file`](Self::from_file), but for diagnostics: a field whose `${VAR}`
+    /// reference is undefined falls back to its default (or an empty string when it
+    /// has none) instead of failing the load. Returns the config and the dotted paths
+    /// of those fields, e.g. `local.dev.port`.
+    pub(crate) fn from_file_skipping_undefined_env(
+        path: &Path,
+    ) -> Result<(Self, Vec<String>), ConfigError> {
         let content = fs::read_to_string(path).map_err(|source| ConfigError::ReadHelixConfig {
             path: path.to_path_buf(),
             source,
         })?;
+        let (config, unresolved) =
+            Self::parse_skipping_undefined_env(&content, path, |name| std::env::var(name).ok())?;
+        config.validate(path, true)?;
+        Ok((config, unresolved))
+    }
 
-        let config: HelixConfig =
-            toml::from_str(&content).map_err(|source| ConfigError::ParseHelixConfig {
+    /// Parsing half of [`from_file_skipping_undefined_env`](Self::from_file_skipping_undefined_env).
+    pub(crate) fn parse_skipping_undefined_env(
+        content: &str,
+        path: &Path,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<(Self, Vec<String>), ConfigError> {
+        let mut value: toml::Value =
+            toml::from_str(content).map_err(|source| ConfigError::ParseHelixConfig {
                 path: path.to_path_buf(),
                 source,
             })?;
+        let mut unresolved = Vec::new();
+        drop_undefined_env_refs(&mut value, &mut Vec::new(), &lookup, &mut unresolved);
+        let content = toml::to_string(&value)
+            .map_err(|source| ConfigError::SerializeHelixConfig { source })?;
+        let config = Self::parse_with_env(&content, path, lookup)?;
+        Ok((config, unresolved))
+    }
 
+    fn from_file_inner(
+        path: &Path,
+        require_instances: bool,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let content = fs::read_to_string(path).map_err(|source| ConfigError::ReadHelixConfig {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        let config = Self::parse_with_env(&content, path, lookup)?;
         config.validate(path, require_instances)?;
         Ok(config)
     }
 
+    /// Parse `helix.toml` content, expanding `${VAR}` and `${VAR:-default}` references in
+    /// string values. A value that is exactly one reference to an integer or boolean
+    /// field takes that type, so `port = "${HELIX_DEV_PORT}"` works.
+    pub(crate) fn parse_with_env(
+        content: &str,
+        path: &Path,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let parse_error = |source| ConfigError::ParseHelixConfig {
+            path: path.to_path_buf(),
+            source,
+        };
+
+        if !content.contains("${") {
+            return toml::from_str(content).map_err(parse_error);
+        }
+
+        let mut value: toml::Value = toml::from_str(content).map_err(parse_error)?;
+        let mut templates = Vec::new();
+        interpolate_value(&mut value, &mut Vec::new(), &lookup, &mut templates).map_err(
+            |error| match error {
+                EnvExpandError::Undefined(name) => ConfigError::UndefinedEnvVar {
+                    name,
+                    path: path.to_path_buf(),
+                },
+                EnvExpandError::Malformed(reference) => ConfigError::InvalidEnvReference {
+                    reference,
+                    path: path.to_path_buf(),
+                },
+            },
+        )?;
+
+        let mut config: HelixConfig = value.try_into().map_err(parse_error)?;
+        config.env_templates = templates;
+        Ok(config)
+    }
+
     pub fn save_to_file(&self, path: &Path) -> Result<(), ConfigError> {
-        let content = toml::to_string_pretty(self)
+        let content = self
+            .to_toml_with_env_refs()
             .map_err(|source| ConfigError::SerializeHelixConfig { source })?;
         fs::write(path, content).map_err(|source| ConfigError::WriteHelixConfig {
             path: path.to_path_buf(),
@@ -378,6 +590,19 @@ impl HelixConfig {
         Ok(())
     }
 
+    /// Serialize with the `${VAR}` references from loading in place of their resolved
+    /// values, so nothing read from the environment ends up in the output.
+    pub(crate) fn to_toml_with_env_refs(&self) -> Result<String, toml::ser::Error> {
+        if self.env_templates.is_empty() {
+            return toml::to_string_pretty(self);
+        }
+        let mut value = toml::Value::try_from(self)?;
+        for template in &self.env_templates {
+            template.restore(&mut value);
+        }
+        toml::to_string_pretty(&value)
+    }
+
     fn validate(&self, path: &Path, require_instances: bool) -> Result<(), ConfigError> {
         let relative_path = std::env::current_dir()
             .ok()
@@ -405,6 +630,17 @@ impl HelixConfig {
             }
         }
 
+        for (name, config) in &self.local {
+            if let Err(reason) = config.validate_image_ref() {
+                return Err(ConfigError::InvalidImageReference {
+                    name: name.clone(),
+                    reference: config.image_ref(),
+                    reason,
+                    path: relative_path.clone(),
+                });
+            }
+        }
+
         for (name, config) in &self.enterprise {
             if config.cluster_id.trim().is_empty() {
                 return Err(ConfigError::MissingClusterId {
@@ -465,14 +701,530 @@ impl HelixConfig {
             },
             local,
             enterprise: HashMap::new(),
+            env_templates: Vec::new(),
         }
     }
 }
 
+impl EnvTemplate {
+    /// Put the `${VAR}` reference back at this template's path, unless the value there
+    /// was changed since loading.
+    fn restore(&self, root: &mut toml::Value) {
+        let mut value = root;
+        for key in &self.path {
+            let next = match value {
+                toml::Value::Table(table) => table.get_mut(key),
+                toml::Value::Array(items) => {
+                    key.parse::<usize>().ok().and_then(|i| items.get_mut(i))
+                }
+                _ => None,
+            };
+            let Some(next) = next else {
+                return;
+            };
+            value = next;
+        }
+        if *value == self.resolved {
+            *value = toml::Value::String(self.template.clone());
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum EnvExpandError {
+    Undefined(String),
+    Malformed(String),
+}
+
+/// Keys of the integer and boolean fields in instance configs. Only these are
+/// coerced from env values, so `tag = "${HELIX_TAG}"` stays a string when the tag
+/// is `2`. Keep in sync with `LocalInstanceConfig`, `EnterpriseInstanceConfig`
+/// and `DbConfig`.
+const TYPED_CONFIG_KEYS: &[&str] = &[
+    "port",
+    "min_instances",
+    "max_instances",
+    "mcp",
+    "bm25",
+    "m",
+    "ef_construction",
+    "ef_search",
+    "db_max_size_gb",
+];
+
+fn interpolate_value(
+    value: &mut toml::Value,
+    path: &mut Vec<String>,
+    lookup: &impl Fn(&str) -> Option<String>,
+    templates: &mut Vec<EnvTemplate>,
+) -> Result<(), EnvExpandError> {
+    match value {
+        toml::Value::String(template) if template.contains("${") => {
+            let expanded = expand_env_refs(template, lookup)?;
+            let is_single_reference = template.starts_with("${")
+                && template.ends_with('}')
+                && template.matches("${").count() == 1;
+            let typed_field = path
+                .last()
+                .is_some_and(|key| TYPED_CONFIG_KEYS.contains(&key.as_str()));
+            let resolved = if !is_single_reference || !typed_field {
+                toml::Value::String(expanded)
+            } else if let Ok(number) = expanded.parse::<i64>() {
+                toml::Value::Integer(number)
+            } else if let Ok(flag) = expanded.parse::<bool>() {
+                toml::Value::Boolean(flag)
+            } else {
+                toml::Value::String(expanded)
+            };
+            templates.push(EnvTemplate {
+                path: path.clone(),
+                template: template.clone(),
+                resolved: resolved.clone(),
+            });
+            *value = resolved;
+        }
+        toml::Value::Table(table) => {
+            for (key, item) in table.iter_mut() {
+                path.push(key.clone());
+                interpolate_value(item, path, lookup, templates)?;
+                path.pop();
+            }
+        }
+        toml::Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                path.push(index.to_string());
+                interpolate_value(item, path, lookup, templates)?;
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace string values with an undefined `${VAR}` reference, recording their paths.
+/// Integer and boolean fields are removed so their serde default applies; other
+/// fields become an empty string, which still satisfies required string fields.
+fn drop_undefined_env_refs(
+    value: &mut toml::Value,
+    path: &mut Vec<String>,
+    lookup: &impl Fn(&str) -> Option<String>,
+    unresolved: &mut Vec<String>,
+) {
+    let is_undefined = |value: &toml::Value| match value {
+        toml::Value::String(template) => matches!(
+            expand_env_refs(template, lookup),
+            Err(EnvExpandError::Undefined(_))
+        ),
+        _ => false,
+    };
+    match value {
+        toml::Value::Table(table) => {
+            let mut typed = Vec::new();
+            for (key, item) in table.iter_mut() {
+                path.push(key.clone());
+                if is_undefined(item) {
+                    unresolved.push(path.join("."));
+                    if TYPED_CONFIG_KEYS.contains(&key.as_str()) {
+                        typed.push(key.clone());
+                    } else {
+                        *item = toml::Value::String(String::new());
+                    }
+                } else {
+                    drop_undefined_env_refs(item, path, lookup, unresolved);
+                }
+                path.pop();
+            }
+            for key in typed {
+                table.remove(&key);
+            }
+        }
+        toml::Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                path.push(index.to_string());
+                if is_undefined(item) {
+                    unresolved.push(path.join("."));
+                    *item = toml::Value::String(String::new());
+                } else {
+                    drop_undefined_env_refs(item, path, lookup, unresolved);
+                }
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Expand every `${VAR}` / `${VAR:-default}` reference in `input`. The default is used
+/// when the variable is unset or empty.
+fn expand_env_refs(
+    input: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<String, EnvExpandError> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            return Err(EnvExpandError::Malformed(rest[start..].to_string()));
+        };
+        let reference = &after[..end];
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+        let valid_name = !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_name {
+            return Err(EnvExpandError::Malformed(format!("${{{reference}}}")));
+        }
+        match (lookup(name).filter(|value| !value.is_empty()), default) {
+            (Some(value), _) => output.push_str(&value),
+            (None, Some(default)) => output.push_str(default),
+            (None, None) => return Err(EnvExpandError::Undefined(name.to_string())),
+        }
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Variables referenced without a default in `helix.toml` string values that `lookup`
+/// can't resolve, in order of first use. Malformed references are left to parsing.
+pub(crate) fn undefined_env_refs(
+    content: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Vec<String> {
+    let Ok(value) = toml::from_str::<toml::Value>(content) else {
+        return Vec::new();
+    };
+    let mut missing = Vec::new();
+    collect_undefined_env_refs(&value, &lookup, &mut missing);
+    missing
+}
+
+fn collect_undefined_env_refs(
+    value: &toml::Value,
+    lookup: &impl Fn(&str) -> Option<String>,
+    missing: &mut Vec<String>,
+) {
+    match value {
+        toml::Value::String(template) if template.contains("${") => loop {
+            // Names already reported resolve to a placeholder so the scan moves past them.
+            let known = |name: &str| {
+                lookup(name).or_else(|| missing.iter().any(|m| m == name).then(|| "-".into()))
+            };
+            match expand_env_refs(template, &known) {
+                Err(EnvExpandError::Undefined(name)) => missing.push(name),
+                _ => break,
+            }
+        },
+        toml::Value::Table(table) => {
+            for item in table.values() {
+                collect_undefined_env_refs(item, lookup, missing);
+            }
+        }
+        toml::Value::Array(items) => {
+            for item in items {
+                collect_undefined_env_refs(item, lookup, missing);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn env_lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    const ENV_CONFIG: &str = r#"
+[project]
+name = "demo"
+
+[local.dev]
+port = "${HELIX_DEV_PORT}"
+image = "${HELIX_REGISTRY:-ghcr.io/helixdb}/enterprise-dev"
+
+[enterprise.production]
+cluster_id = "${HELIX_CLUSTER_ID}"
+"#;
+
+    #[test]
+    fn image_ref_defaults_to_enterprise_dev_image() {
+        let config: HelixConfig = toml::from_str(
+            r#"
+[project]
+name = "demo"
+
+[local.dev]
+port = 6970
+"#,
+        )
+        .unwrap();
+
+        let dev = config.local.get("dev").unwrap();
+        assert_eq!(
+            dev.image_ref(),
+            format!("{DEFAULT_ENTERPRISE_DEV_IMAGE}:{DEFAULT_ENTERPRISE_DEV_TAG}")
+        );
+        assert_eq!(dev.validate_image_ref(), Ok(()));
+    }
+
+    #[test]
+    fn image_ref_uses_custom_registry_and_tag() {
+        let config: HelixConfig = toml::from_str(
+            r#"
+[project]
+name = "demo"
+
+[local.dev]
+image = "registry.internal:5000/platform/helix-enterprise"
+tag = "3.1.0-rc.1"
+"#,
+        )
+        .unwrap();
+
+        let dev = config.local.get("dev").unwrap();
+        assert_eq!(
+            dev.image_ref(),
+            "registry.internal:5000/platform/helix-enterprise:3.1.0-rc.1"
+        );
+        assert_eq!(dev.validate_image_ref(), Ok(()));
+    }
+
+    #[test]
+    fn image_ref_pins_a_digest_from_tag() {
+        let digest = format!("sha256:{}", "0123456789abcdef".repeat(4));
+        for tag in [digest.clone(), format!("@{digest}")] {
+            let dev = LocalInstanceConfig {
+                image: "ghcr.io/helixdb/enterprise-dev".to_string(),
+                tag,
+                ..LocalInstanceConfig::default()
+            };
+            assert_eq!(dev.validate_image_ref(), Ok(()));
+            assert_eq!(
+                dev.image_ref(),
+                format!("ghcr.io/helixdb/enterprise-dev@{digest}")
+            );
+        }
+
+        for tag in ["sha256:abc", "@sha256:", &digest.to_uppercase()[..]] {
+            let dev = LocalInstanceConfig {
+                tag: tag.to_string(),
+                ..LocalInstanceConfig::default()
+            };
+            assert!(
+                dev.validate_image_ref().is_err(),
+                "{tag} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_image_references_are_rejected() {
+        let instance = |image: &str, tag: &str| LocalInstanceConfig {
+            image: image.to_string(),
+            tag: tag.to_string(),
+            ..LocalInstanceConfig::default()
+        };
+
+        for (image, tag) in [
+            ("", "latest"),
+            ("ghcr.io/HelixDB/enterprise-dev", "latest"),
+            ("ghcr.io/helixdb/enterprise-dev:v1", "latest"),
+            ("ghcr.io/helixdb//enterprise-dev", "latest"),
+            ("registry:port/helix", "latest"),
+            ("ghcr.io/helixdb/enterprise-dev", ""),
+            ("ghcr.io/helixdb/enterprise-dev", "-rc"),
+            ("ghcr.io/helixdb/enterprise-dev", "v1 beta"),
+        ] {
+            assert!(
+                instance(image, tag).validate_image_ref().is_err(),
+                "{image}:{tag} should be rejected"
+            );
+        }
+
+        let mut config = HelixConfig::default_config("demo");
+        config.local.get_mut("dev").unwrap().tag = "not valid".to_string();
+        assert!(matches!(
+            config.validate(Path::new("helix.toml"), true),
+            Err(ConfigError::InvalidImageReference { ref name, .. }) if name == "dev"
+        ));
+    }
+
+    #[test]
+    fn env_references_expand_from_environment() {
+        let config = HelixConfig::parse_with_env(
+            ENV_CONFIG,
+            Path::new("helix.toml"),
+            env_lookup(&[
+                ("HELIX_DEV_PORT", "7001"),
+                ("HELIX_REGISTRY", "registry.internal"),
+                ("HELIX_CLUSTER_ID", "cluster-abc"),
+            ]),
+        )
+        .unwrap();
+
+        let dev = config.local.get("dev").unwrap();
+        assert_eq!(dev.port, 7001);
+        assert_eq!(dev.image, "registry.internal/enterprise-dev");
+        assert_eq!(
+            config.enterprise.get("production").unwrap().cluster_id,
+            "cluster-abc"
+        );
+    }
+
+    #[test]
+    fn env_reference_default_used_when_variable_unset() {
+        let config = HelixConfig::parse_with_env(
+            ENV_CONFIG,
+            Path::new("helix.toml"),
+            env_lookup(&[
+                ("HELIX_DEV_PORT", "7001"),
+                ("HELIX_CLUSTER_ID", "cluster-abc"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.local.get("dev").unwrap().image,
+            "ghcr.io/helixdb/enterprise-dev"
+        );
+    }
+
+    #[test]
+    fn env_references_only_coerce_numeric_and_bool_fields() {
+        let config = HelixConfig::parse_with_env(
+            r#"
+[project]
+name = "demo"
+
+[local.dev]
+port = "${HELIX_DEV_PORT}"
+tag = "${HELIX_TAG}"
+
+[enterprise.production]
+cluster_id = "${HELIX_CLUSTER_ID}"
+mcp = "${HELIX_MCP}"
+min_instances = "${HELIX_MIN_INSTANCES}"
+"#,
+            Path::new("helix.toml"),
+            env_lookup(&[
+                ("HELIX_DEV_PORT", "7001"),
+                ("HELIX_TAG", "2"),
+                ("HELIX_MCP", "false"),
+                ("HELIX_CLUSTER_ID", "true"),
+                ("HELIX_MIN_INSTANCES", "3"),
+            ]),
+        )
+        .unwrap();
+
+        let dev = config.local.get("dev").unwrap();
+        assert_eq!(dev.port, 7001);
+        assert_eq!(dev.tag, "2");
+        let production = config.enterprise.get("production").unwrap();
+        assert!(!production.db_config.mcp);
+        assert_eq!(production.cluster_id, "true");
+        assert_eq!(production.min_instances, 3);
+    }
+
+    #[test]
+    fn skipping_undefined_env_falls_back_to_defaults_and_reports_fields() {
+        let (config, unresolved) = HelixConfig::parse_skipping_undefined_env(
+            r#"
+[project]
+name = "demo"
+
+[local.dev]
+port = "${HELIX_DEV_PORT}"
+tag = "${HELIX_TAG}"
+
+[enterprise.production]
+cluster_id = "${HELIX_CLUSTER_ID}"
+mcp = "${HELIX_MCP}"
+bm25 = "${HELIX_BM25:-false}"
+"#,
+            Path::new("helix.toml"),
+            env_lookup(&[]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            unresolved,
+            vec![
+                "local.dev.port",
+                "local.dev.tag",
+                "enterprise.production.cluster_id",
+                "enterprise.production.mcp",
+            ]
+        );
+        assert_eq!(config.local.get("dev").unwrap().port, DEFAULT_LOCAL_PORT);
+        let production = config.enterprise.get("production").unwrap();
+        assert!(production.db_config.mcp);
+        assert!(!production.db_config.bm25);
+    }
+
+    #[test]
+    fn undefined_env_reference_is_an_error() {
+        let error = HelixConfig::parse_with_env(
+            ENV_CONFIG,
+            Path::new("helix.toml"),
+            env_lookup(&[("HELIX_DEV_PORT", "7001")]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ConfigError::UndefinedEnvVar { ref name, .. } if name == "HELIX_CLUSTER_ID"
+        ));
+        assert_eq!(
+            expand_env_refs("${UNCLOSED", &env_lookup(&[])),
+            Err(EnvExpandError::Malformed("${UNCLOSED".to_string()))
+        );
+    }
+
+    #[test]
+    fn saving_keeps_env_references_instead_of_resolved_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("helix.toml");
+        let mut config = HelixConfig::parse_with_env(
+            ENV_CONFIG,
+            &path,
+            env_lookup(&[
+                ("HELIX_DEV_PORT", "7001"),
+                ("HELIX_CLUSTER_ID", "secret-id"),
+            ]),
+        )
+        .unwrap();
+        config.local.get_mut("dev").unwrap().tag = "v2".to_string();
+        config.save_to_file(&path).unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains(r#"port = "${HELIX_DEV_PORT}""#), "{saved}");
+        assert!(
+            saved.contains(r#"cluster_id = "${HELIX_CLUSTER_ID}""#),
+            "{saved}"
+        );
+        assert!(saved.contains(r#"tag = "v2""#), "{saved}");
+        assert!(!saved.contains("secret-id"), "{saved}");
+        // Restoring goes through `toml::Value`; the file keeps the struct's key order.
+        assert!(
+            saved.find("[project]") < saved.find("[local.dev]"),
+            "{saved}"
+        );
+        assert!(saved.find("port =") < saved.find("image ="), "{saved}");
+    }
+
     #[test]
     fn old_enterprise_config_defaults_queries_and_runtime_fields() {
         let config: HelixConfig = toml::from_str(
@@ -591,4 +1343,45 @@ storage = "disk"
         let local = config.local.get("dev").unwrap();
         assert_eq!(local.storage, LocalStorageMode::Disk);
     }
+
+    #[test]
+    fn local_config_parses_and_round_trips_log_level() {
+        let config: HelixConfig = toml::from_str(
+            r#"
+[project]
+name = "demo"
+
+[local.dev]
+log_level = "debug"
+"#,
+        )
+        .expect("log_level should deserialize");
+
+        let local = config.local.get("dev").unwrap();
+        assert_eq!(local.log_level, Some(LogLevel::Debug));
+        assert!(
+            toml::to_string(&config)
+                .unwrap()
+                .contains("log_level = \"debug\"")
+        );
+
+        let unset = LocalInstanceConfig::default();
+        assert_eq!(unset.log_level, None);
+        assert!(!toml::to_string(&unset).unwrap().contains("log_level"));
+    }
+
+    #[test]
+    fn local_config_rejects_unknown_log_level() {
+        let err = toml::from_str::<HelixConfig>(
+            r#"
+[project]
+name = "demo"
+
+[local.dev]
+log_level = "verbose"
+"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown variant"), "{err}");
+    }
 }
@@ -245,6 +245,11 @@ pub struct CliEnterpriseCluster {
     pub min_instances: Option<u64>,
     #[serde(default)]
     pub max_instances: Option<u64>,
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Deployment state, when the Cloud API reports one.
+    #[serde(default, alias = "deployment_status")]
+    pub status: Option<String>,
 }
 
 impl CliEnterpriseCluster {
@@ -142,6 +142,59 @@ impl CliError {
     }
 }
 
+impl CliError {
+    /// Machine-readable form of this error, printed by `--output json`.
+    pub fn to_json(&self, category: ErrorCategory) -> serde_json::Value {
+        serde_json::json!({
+            "category": category.as_str(),
+            "severity": self.severity.label(),
+            "message": self.message,
+            "context": self.context,
+            "hint": self.hint,
+            "file_path": self.file_path,
+            "caused_by": self.caused_by,
+        })
+    }
+}
+
+/// Which error type a command failure came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    Cli,
+    Config,
+    Project,
+    Port,
+    Other,
+}
+
+impl ErrorCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::Cli => "cli",
+            ErrorCategory::Config => "config",
+            ErrorCategory::Project => "project",
+            ErrorCategory::Port => "port",
+            ErrorCategory::Other => "other",
+        }
+    }
+}
+
+/// Categorize a command failure and convert it into a [`CliError`]. Errors that are not
+/// one of this module's types become a plain [`CliError`] carrying their message.
+pub fn classify_error(error: &eyre::Report) -> (ErrorCategory, CliError) {
+    if let Some(cli_error) = error.downcast_ref::<CliError>() {
+        (ErrorCategory::Cli, cli_error.clone())
+    } else if let Some(config_error) = error.downcast_ref::<ConfigError>() {
+        (ErrorCategory::Config, config_error.to_cli_error())
+    } else if let Some(project_error) = error.downcast_ref::<ProjectError>() {
+        (ErrorCategory::Project, project_error.to_cli_error())
+    } else if let Some(port_error) = error.downcast_ref::<PortError>() {
+        (ErrorCategory::Port, port_error.to_cli_error())
+    } else {
+        (ErrorCategory::Other, CliError::new(error.to_string()))
+    }
+}
+
 impl fmt::Display for CliError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.render())
@@ -214,6 +267,17 @@ pub enum ConfigError {
     EmptyInstanceName { path: PathBuf },
     #[error("Enterprise instance '{name}' must have a non-empty cluster_id in {path}")]
     MissingClusterId { name: String, path: PathBuf },
+    #[error("invalid image reference '{reference}' for instance '{name}' in {path}: {reason}")]
+    InvalidImageReference {
+        name: String,
+        reference: String,
+        reason: String,
+        path: PathBuf,
+    },
+    #[error("environment variable '{name}' referenced in {path} is not set")]
+    UndefinedEnvVar { name: String, path: PathBuf },
+    #[error("invalid environment variable reference '{reference}' in {path}")]
+    InvalidEnvReference { reference: String, path: PathBuf },
     #[error("instance '{name}' not found in helix.toml")]
     InstanceNotFound { name: String },
 }
@@ -313,6 +377,33 @@ impl ConfigError {
             ConfigError::InstanceNotFound { name } => {
                 CliError::new(format!("instance '{}' not found in helix.toml", name))
             }
+            ConfigError::InvalidImageReference {
+                name,
+                reference,
+                reason,
+                path,
+            } => CliError::new(format!(
+                "invalid image reference '{}' for instance '{}' in {}",
+                reference,
+                name,
+                path.display()
+            ))
+            .with_caused_by(reason.clone())
+            .with_hint("set `image` to a repository such as `ghcr.io/helixdb/enterprise-dev` and `tag` to a version such as `latest`"),
+            ConfigError::UndefinedEnvVar { name, path } => CliError::new(format!(
+                "environment variable '{}' referenced in {} is not set",
+                name,
+                path.display()
+            ))
+            .with_hint(format!(
+                "export {name}, or give a fallback with `${{{name}:-default}}`"
+            )),
+            ConfigError::InvalidEnvReference { reference, path } => CliError::new(format!(
+                "invalid environment variable reference '{}' in {}",
+                reference,
+                path.display()
+            ))
+            .with_hint("use `${VAR}` or `${VAR:-default}` with a name made of letters, digits and underscores"),
         }
     }
 }
@@ -164,6 +164,14 @@ pub enum ConfigOutputFormat {
     Json,
 }
 
+/// Format of the global `--output` flag, which controls how errors are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+}
+
 #[derive(Subcommand)]
 pub enum ConfigAction {
     /// Manage active workspace selection
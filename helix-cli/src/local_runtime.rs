@@ -1,8 +1,9 @@
-use crate::config::{ContainerRuntime, LocalInstanceConfig};
+use crate::config::{ContainerRuntime, LocalInstanceConfig, LogLevel};
 use crate::errors::CliError;
 use crate::output::Step;
 use crate::project::ProjectContext;
 use crate::utils::command_exists;
+use chrono::{DateTime, Utc};
 use eyre::{Result, eyre};
 use std::io::{Read, Write};
 use std::net::TcpStream;
@@ -39,6 +40,13 @@ pub struct LocalStatus {
     pub ports: String,
 }
 
+/// Point-in-time container resource usage as reported by `<runtime> stats`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalResourceUsage {
+    pub cpu: String,
+    pub memory: String,
+}
+
 #[derive(Debug, Clone)]
 struct DiskRuntimeResources {
     minio_container: String,
@@ -209,7 +217,14 @@ impl LocalRuntime {
             None
         };
 
-        let args = helix_run_args(&name, &image, config.port, true, disk_resources.as_ref());
+        let args = helix_run_args(
+            &name,
+            &image,
+            config.port,
+            true,
+            config.log_level,
+            disk_resources.as_ref(),
+        );
         let output = Command::new(self.runtime.binary())
             .args(&args)
             .output()
@@ -241,7 +256,14 @@ impl LocalRuntime {
             let _ = self.remove_disk_resources(instance_name, false);
             None
         };
-        let args = helix_run_args(&name, &image, config.port, false, disk_resources.as_ref());
+        let args = helix_run_args(
+            &name,
+            &image,
+            config.port,
+            false,
+            config.log_level,
+            disk_resources.as_ref(),
+        );
 
         let mut child = TokioCommand::new(self.runtime.binary())
             .args(&args)
@@ -291,32 +313,33 @@ impl LocalRuntime {
         Ok(removed_helix || removed_disk_resources)
     }
 
+    /// Recreate the instance's container. `<runtime> restart` would keep the port,
+    /// image and environment (such as `RUST_LOG` from `log_level`) the container was
+    /// created with, ignoring edits to helix.toml. Memory storage is lost on either
+    /// path, and disk data lives in its own volume.
     pub fn restart(&self, instance_name: &str, config: &LocalInstanceConfig) -> Result<()> {
-        if config.storage.is_disk() {
-            return self.run_detached(instance_name, config);
-        }
-
-        let name = self.container_name(instance_name);
-        let output = Command::new(self.runtime.binary())
-            .args(["restart", &name])
-            .output()
-            .map_err(|e| eyre!("Failed to restart {name}: {e}"))?;
-
-        if output.status.success() {
-            self.wait_ready(config.port)?;
-            return Ok(());
-        }
-
         self.run_detached(instance_name, config)
     }
 
-    pub fn logs(&self, instance_name: &str, follow: bool) -> Result<()> {
+    pub fn logs(
+        &self,
+        instance_name: &str,
+        follow: bool,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<()> {
         let name = self.container_name(instance_name);
         let mut command = Command::new(self.runtime.binary());
         command.arg("logs");
         if follow {
             command.arg("-f");
         }
+        if let Some(since) = since {
+            command.args(["--since", &since.to_rfc3339()]);
+        }
+        if let Some(until) = until {
+            command.args(["--until", &until.to_rfc3339()]);
+        }
         command.arg(&name);
         let status = command
             .stdin(Stdio::inherit())
@@ -370,6 +393,59 @@ impl LocalRuntime {
         }))
     }
 
+    /// Sample CPU/memory usage for a running instance container. Returns `None`
+    /// when the container isn't running or the runtime reports nothing for it.
+    pub fn resource_usage(&self, instance_name: &str) -> Result<Option<LocalResourceUsage>> {
+        let name = self.container_name(instance_name);
+        let output = Command::new(self.runtime.binary())
+            .args([
+                "stats",
+                "--no-stream",
+                "--format",
+                "{{.CPUPerc}}\t{{.MemUsage}}",
+                &name,
+            ])
+            .output()
+            .map_err(|e| eyre!("Failed to read resource usage for {name}: {e}"))?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout.lines().find_map(parse_stats_line))
+    }
+
+    /// Output of `<runtime> system df -v`, which lists every volume's size. It is
+    /// slow, so read it once and look each instance up with
+    /// [`data_volume_size`](Self::data_volume_size).
+    pub fn disk_usage_report(&self) -> Option<String> {
+        let json = Command::new(self.runtime.binary())
+            .args(["system", "df", "-v", "--format", "json"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+            .filter(|stdout| serde_json::from_str::<serde_json::Value>(stdout.trim()).is_ok());
+        if json.is_some() {
+            return json;
+        }
+        // Podman doesn't combine --verbose with --format; read its table instead.
+        Command::new(self.runtime.binary())
+            .args(["system", "df", "-v"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Size of a disk-mode instance's MinIO data volume in a
+    /// [`disk_usage_report`](Self::disk_usage_report) (e.g. `12.5MB`). Returns
+    /// `None` when the volume isn't listed.
+    pub fn data_volume_size(&self, report: &str, instance_name: &str) -> Option<String> {
+        parse_volume_size(report, &self.disk_resources(instance_name).volume)
+    }
+
     pub fn prune_instance(&self, instance_name: &str) -> Result<bool> {
         let name = self.container_name(instance_name);
         let removed_helix = self.remove_container(&name)?;
@@ -692,6 +768,7 @@ fn helix_run_args(
     image: &str,
     port: u16,
     detached: bool,
+    log_level: Option<LogLevel>,
     disk_resources: Option<&DiskRuntimeResources>,
 ) -> Vec<String> {
     let mut args = vec!["run".to_string()];
@@ -712,6 +789,10 @@ fn helix_run_args(
         format!("{port}:{CONTAINER_PORT}"),
     ]);
 
+    if let Some(level) = log_level {
+        args.extend(["-e".to_string(), format!("RUST_LOG={}", level.as_str())]);
+    }
+
     if let Some(resources) = disk_resources {
         args.extend(["--network".to_string(), resources.network.clone()]);
         for (key, value) in disk_env(resources) {
@@ -785,6 +866,57 @@ fn disk_env(resources: &DiskRuntimeResources) -> Vec<(&'static str, String)> {
     ]
 }
 
+fn parse_stats_line(line: &str) -> Option<LocalResourceUsage> {
+    let (cpu, memory) = line.trim().split_once('\t')?;
+    let (cpu, memory) = (cpu.trim(), memory.trim());
+    if cpu.is_empty() || memory.is_empty() {
+        return None;
+    }
+    Some(LocalResourceUsage {
+        cpu: cpu.to_string(),
+        memory: memory.to_string(),
+    })
+}
+
+/// Find `volume`'s size in `system df -v` output: either Docker's JSON document
+/// (`{"Volumes": [{"Name": .., "Size": ..}]}`) or the plain table, where volume
+/// rows follow a `VOLUME NAME ... SIZE` header.
+fn parse_volume_size(output: &str, volume: &str) -> Option<String> {
+    if let Ok(document) = serde_json::from_str::<serde_json::Value>(output.trim()) {
+        return document
+            .get("Volumes")?
+            .as_array()?
+            .iter()
+            .find(|entry| entry.get("Name").and_then(|name| name.as_str()) == Some(volume))
+            .and_then(|entry| match entry.get("Size")? {
+                serde_json::Value::String(size) => Some(size.clone()),
+                serde_json::Value::Number(size) => Some(format!("{size}B")),
+                _ => None,
+            });
+    }
+
+    let mut in_volumes = false;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("VOLUME NAME") {
+            in_volumes = true;
+            continue;
+        }
+        if !in_volumes {
+            continue;
+        }
+        if trimmed.is_empty() {
+            in_volumes = false;
+            continue;
+        }
+        let mut columns = trimmed.split_whitespace();
+        if columns.next() == Some(volume) {
+            return columns.next_back().map(str::to_string);
+        }
+    }
+    None
+}
+
 fn missing_resource(stderr: &str) -> bool {
     let stderr = stderr.to_ascii_lowercase();
     stderr.contains("no such") || stderr.contains("not found") || stderr.contains("does not exist")
@@ -819,6 +951,7 @@ mod tests {
             9090,
             true,
             None,
+            None,
         );
 
         assert_eq!(
@@ -848,6 +981,7 @@ mod tests {
             "ghcr.io/helixdb/enterprise-dev:latest",
             8080,
             true,
+            None,
             Some(&resources),
         );
 
@@ -861,6 +995,24 @@ mod tests {
         assert!(args.contains(&"AWS_ALLOW_HTTP=true".to_string()));
     }
 
+    #[test]
+    fn helix_args_pass_log_level_as_rust_log() {
+        let args = helix_run_args(
+            "helix-demo-dev",
+            "ghcr.io/helixdb/enterprise-dev:latest",
+            8080,
+            false,
+            Some(LogLevel::Debug),
+            None,
+        );
+
+        assert!(has_pair(&args, "-e", "RUST_LOG=debug"));
+        assert_eq!(
+            args.last().map(String::as_str),
+            Some("ghcr.io/helixdb/enterprise-dev:latest")
+        );
+    }
+
     #[test]
     fn minio_args_include_persistent_volume() {
         let resources = disk_resources();
@@ -882,6 +1034,44 @@ mod tests {
         assert!(args.iter().any(|arg| arg.contains("mc alias set local")));
     }
 
+    #[test]
+    fn volume_size_reads_docker_json_and_table_output() {
+        let json = r#"{"Images":[],"Containers":[],"Volumes":[
+            {"Name":"other","Links":"0","Size":"1GB"},
+            {"Name":"helix-demo-dev-minio-data","Links":"1","Size":"12.5MB"}
+        ],"BuildCache":[]}"#;
+        assert_eq!(
+            parse_volume_size(json, "helix-demo-dev-minio-data"),
+            Some("12.5MB".to_string())
+        );
+        assert_eq!(parse_volume_size(json, "missing"), None);
+
+        let table = "Images space usage:\n\n\
+                     REPOSITORY   TAG   IMAGE ID   CREATED   SIZE   SHARED SIZE   UNIQUE SIZE   CONTAINERS\n\n\
+                     Local Volumes space usage:\n\n\
+                     VOLUME NAME                 LINKS     SIZE\n\
+                     helix-demo-dev-minio-data   1         48.2kB\n\
+                     other                       0         0B\n";
+        assert_eq!(
+            parse_volume_size(table, "helix-demo-dev-minio-data"),
+            Some("48.2kB".to_string())
+        );
+        assert_eq!(parse_volume_size(table, "helix-demo-dev"), None);
+    }
+
+    #[test]
+    fn stats_line_parses_cpu_and_memory() {
+        assert_eq!(
+            parse_stats_line("0.52%\t21.3MiB / 7.6GiB\n"),
+            Some(LocalResourceUsage {
+                cpu: "0.52%".to_string(),
+                memory: "21.3MiB / 7.6GiB".to_string(),
+            })
+        );
+        assert_eq!(parse_stats_line(""), None);
+        assert_eq!(parse_stats_line("--\t"), None);
+    }
+
     fn start_cmd(
         os: &str,
         runtime: ContainerRuntime,
@@ -1,11 +1,11 @@
 use clap::builder::styling::{AnsiColor, Color, RgbColor, Style, Styles};
 use clap::{ArgGroup, Parser, Subcommand};
 use color_eyre::owo_colors::OwoColorize;
-use eyre::Result;
+use eyre::{Result, WrapErr};
 use helix_cli::{
     AddTarget, AuthAction, ClusterConfigAction, ConfigAction, InitTarget, MetricsAction,
-    ProjectConfigAction, SkillsAction, WorkspaceConfigAction, commands, errors, metrics_sender,
-    output, update,
+    OutputFormat, ProjectConfigAction, SkillsAction, WorkspaceConfigAction, commands, errors,
+    metrics_sender, output, update,
 };
 use std::io::IsTerminal;
 use tui_banner::{Align, Banner, ColorMode, Fill, Gradient, Palette};
@@ -54,6 +54,14 @@ struct Cli {
     #[arg(short, long, global = true)]
     verbose: bool,
 
+    /// Skip the CLI and skills update checks for this run
+    #[arg(long, global = true)]
+    no_update_check: bool,
+
+    /// Error output format; `json` prints failures as a JSON object on stderr
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Human)]
+    output: OutputFormat,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -128,6 +136,9 @@ enum Commands {
         instance: Option<String>,
     },
 
+    /// Check the container runtime, helix.toml, environment and ports
+    Doctor,
+
     /// View logs for a local or Enterprise Cloud instance
     Logs {
         /// Instance name
@@ -138,14 +149,36 @@ enum Commands {
         /// Query historical logs with time range for Enterprise Cloud
         #[arg(long, short = 'r')]
         range: bool,
-        /// Start time (ISO 8601)
-        #[arg(long, requires = "range")]
+        /// Start time (ISO 8601, or relative like 30m, 2h, "2 days ago")
+        #[arg(long, visible_alias = "since")]
         start: Option<String>,
-        /// End time (ISO 8601)
-        #[arg(long, requires = "range")]
+        /// End time (ISO 8601, or relative like 30m, 2h, "2 days ago")
+        #[arg(long, visible_alias = "until")]
         end: Option<String>,
     },
 
+    /// Insert nodes from a CSV file into a running Helix instance
+    #[command(
+        after_help = "Examples:\n  helix import-csv dev people.csv --label Person\n  helix import-csv dev people.csv --label Person --map Name=name --map Age=age:i64"
+    )]
+    ImportCsv {
+        /// Instance name
+        instance: String,
+        /// CSV file with a header row
+        file: String,
+        /// Label of the nodes to create
+        #[arg(long)]
+        label: String,
+        /// Map a column to a node field: COLUMN=FIELD[:string|i64|f64|bool].
+        /// Without any --map, every column is imported as a string field. Empty
+        /// cells in i64, f64 and bool columns are imported as null.
+        #[arg(long, value_name = "COLUMN=FIELD[:TYPE]")]
+        map: Vec<String>,
+        /// Rows per write request
+        #[arg(long, default_value_t = commands::import_csv::DEFAULT_BATCH_SIZE)]
+        batch_size: usize,
+    },
+
     /// Send a query to a running Helix instance
     #[command(group(
         ArgGroup::new("query_input")
@@ -159,6 +192,7 @@ enum Commands {
     #[command(disable_help_flag = true)]
     #[command(after_help = r#"Examples:
   helix query --file examples/request.json
+  echo '{"name":"Ada"}' | helix query --file examples/request.json --params -
   helix query -e 'readBatch().varAs("c", g().nWithLabel("User").count()).returning(["c"])'
 
 Docs: https://docs.helix-db.com/cli/command-reference/query"#)]
@@ -194,6 +228,9 @@ Docs: https://docs.helix-db.com/cli/command-reference/query"#)]
             help_heading = "Input (pick one)"
         )]
         ts_file: Option<String>,
+        /// Query parameters as a JSON object: inline, `@params.json`, or `-` for stdin
+        #[arg(long, value_name = "JSON|@FILE|-", help_heading = "Parameters")]
+        params: Option<String>,
         /// Override the host (local instances only)
         #[arg(long, value_name = "HOST", help_heading = "Connection")]
         host: Option<String>,
@@ -215,6 +252,9 @@ Docs: https://docs.helix-db.com/cli/command-reference/query"#)]
         /// Deprecated Helix Cloud dev deploy override; ignored for Enterprise deploys
         #[arg(long, hide = true)]
         dev: bool,
+        /// Build the deploy and show what would be sent without deploying
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Enterprise Cloud auth operations
@@ -562,12 +602,19 @@ fn print_help() {
         use_color,
     );
     print_command_w("logs", "View or follow instance logs", W, use_color);
+    print_command_w(
+        "doctor",
+        "Diagnose runtime, config and port problems",
+        W,
+        use_color,
+    );
     print_command_w(
         "query",
         "Send a dynamic query to POST /v1/query",
         W,
         use_color,
     );
+    print_command_w("import-csv", "Insert nodes from a CSV file", W, use_color);
     print_command_w(
         "prune",
         "Remove Helix-owned local containers and state",
@@ -606,16 +653,40 @@ fn print_help() {
     print_command_w("feedback", "Send feedback to the Helix team", W, use_color);
     print_command_w("help", "Show this help", W, use_color);
 
+    // Options get their own column so the longest flag fits.
+    const OPTIONS_W: usize = 18;
     print_section("Options", use_color);
-    print_command_w("--quiet", "Errors and final result only", W, use_color);
+    print_command_w(
+        "--quiet",
+        "Errors and final result only",
+        OPTIONS_W,
+        use_color,
+    );
     print_command_w(
         "-v, --verbose",
         "Detailed output with timing information",
-        W,
+        OPTIONS_W,
+        use_color,
+    );
+    print_command_w(
+        "--output json",
+        "Print errors as a JSON object",
+        OPTIONS_W,
+        use_color,
+    );
+    print_command_w(
+        "--no-update-check",
+        "Skip the CLI and skills update checks",
+        OPTIONS_W,
+        use_color,
+    );
+    print_command_w("-h, --help", "Show this help", OPTIONS_W, use_color);
+    print_command_w(
+        "-V, --version",
+        "Show the CLI version",
+        OPTIONS_W,
         use_color,
     );
-    print_command_w("-h, --help", "Show this help", W, use_color);
-    print_command_w("-V, --version", "Show the CLI version", W, use_color);
 
     println!();
     println!("Run 'helix <command> --help' for details on a specific command.");
@@ -635,14 +706,36 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    let cli = Cli::parse();
+    output::Verbosity::set(output::Verbosity::from_flags(cli.quiet, cli.verbose));
+    if cli.no_update_check {
+        update::skip_update_checks();
+    }
+
+    let output_format = cli.output;
+    if let Err(e) = run(cli).await {
+        let (category, cli_error) = errors::classify_error(&e);
+        match (output_format, category) {
+            (OutputFormat::Json, _) => eprintln!("{}", cli_error.to_json(category)),
+            (OutputFormat::Human, errors::ErrorCategory::Other) => eprintln!("{e}"),
+            (OutputFormat::Human, _) => eprint!("{}", cli_error.render()),
+        }
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Everything after argument parsing, so any failure (including metrics and
+/// update-check setup) is reported in the format chosen by `--output`.
+async fn run(cli: Cli) -> Result<()> {
     let metrics_sender = metrics_sender::MetricsSender::new()?;
     metrics_sender.send_cli_install_event_if_first_time();
-    let update_available = update::check_for_updates().await?;
+    let update_available = update::check_for_updates()
+        .await
+        .wrap_err("Failed to check for CLI updates")?;
     let skills_update_available = update::check_skills_update().await;
 
-    let cli = Cli::parse();
-    output::Verbosity::set(output::Verbosity::from_flags(cli.quiet, cli.verbose));
-
     let result = match cli.command {
         None => {
             display_welcome(update_available, skills_update_available);
@@ -676,6 +769,7 @@ async fn main() -> Result<()> {
         Some(Commands::Stop { instance }) => commands::stop::run(instance).await,
         Some(Commands::Restart { instance }) => commands::restart::run(instance).await,
         Some(Commands::Status { instance }) => commands::status::run(instance).await,
+        Some(Commands::Doctor) => commands::doctor::run().await,
         Some(Commands::Logs {
             instance,
             follow,
@@ -683,23 +777,36 @@ async fn main() -> Result<()> {
             start,
             end,
         }) => commands::logs::run(instance, follow, range, start, end).await,
+        Some(Commands::ImportCsv {
+            instance,
+            file,
+            label,
+            map,
+            batch_size,
+        }) => commands::import_csv::run(instance, file, label, map, batch_size).await,
         Some(Commands::Query {
             instance,
             file,
             json,
             ts,
             ts_file,
+            params,
             warm,
             host,
             port,
             compact,
             ..
         }) => {
-            commands::query::run(instance, file, json, ts, ts_file, warm, host, port, compact).await
-        }
-        Some(Commands::Push { instance, dev }) => {
-            commands::push::run(instance, dev, &metrics_sender).await
+            commands::query::run(
+                instance, file, json, ts, ts_file, params, warm, host, port, compact,
+            )
+            .await
         }
+        Some(Commands::Push {
+            instance,
+            dev,
+            dry_run,
+        }) => commands::push::run(instance, dev, dry_run, &metrics_sender).await,
         Some(Commands::Auth { action }) => commands::auth::run(action).await,
         Some(Commands::Config { action }) => commands::config::run(action).await,
         Some(Commands::Workspace { action }) => commands::config::run_workspace(action).await,
@@ -724,23 +831,7 @@ async fn main() -> Result<()> {
     };
 
     metrics_sender.shutdown().await?;
-
-    if let Err(e) = result {
-        if let Some(cli_error) = e.downcast_ref::<errors::CliError>() {
-            eprint!("{}", cli_error.render());
-        } else if let Some(config_error) = e.downcast_ref::<errors::ConfigError>() {
-            eprint!("{}", config_error.to_cli_error().render());
-        } else if let Some(project_error) = e.downcast_ref::<errors::ProjectError>() {
-            eprint!("{}", project_error.to_cli_error().render());
-        } else if let Some(port_error) = e.downcast_ref::<errors::PortError>() {
-            eprint!("{}", port_error.to_cli_error().render());
-        } else {
-            eprintln!("{e}");
-        }
-        std::process::exit(1);
-    }
-
-    Ok(())
+    result
 }
 
 #[cfg(test)]
@@ -1233,6 +1324,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn query_accepts_params_alongside_query_input() {
+        let cli = Cli::parse_from([
+            "helix",
+            "query",
+            "dev",
+            "-e",
+            "readBatch()",
+            "--params",
+            "-",
+        ]);
+
+        match cli.command {
+            Some(Commands::Query { ts, params, .. }) => {
+                assert_eq!(ts.as_deref(), Some("readBatch()"));
+                assert_eq!(params.as_deref(), Some("-"));
+            }
+            _ => panic!("expected query command"),
+        }
+        assert!(Cli::try_parse_from(["helix", "query", "dev", "--params", "-"]).is_err());
+    }
+
+    #[test]
+    fn no_update_check_is_a_global_flag() {
+        assert!(Cli::parse_from(["helix", "status", "--no-update-check"]).no_update_check);
+        assert!(Cli::parse_from(["helix", "--no-update-check", "status"]).no_update_check);
+        assert!(!Cli::parse_from(["helix", "status"]).no_update_check);
+    }
+
     #[test]
     fn query_accepts_inline_json_input() {
         let inline_json = r#"{"request_type":"read","query":{"queries":[]}}"#;
@@ -1273,14 +1393,29 @@ mod tests {
         let cli = Cli::parse_from(["helix", "push", "production"]);
 
         match cli.command {
-            Some(Commands::Push { instance, dev }) => {
+            Some(Commands::Push {
+                instance,
+                dev,
+                dry_run,
+            }) => {
                 assert_eq!(instance.as_deref(), Some("production"));
                 assert!(!dev);
+                assert!(!dry_run);
             }
             _ => panic!("expected push command"),
         }
     }
 
+    #[test]
+    fn push_accepts_dry_run() {
+        let cli = Cli::parse_from(["helix", "push", "production", "--dry-run"]);
+
+        match cli.command {
+            Some(Commands::Push { dry_run, .. }) => assert!(dry_run),
+            _ => panic!("expected push command"),
+        }
+    }
+
     #[test]
     fn sync_accepts_yes_for_noninteractive_reconciliation() {
         let cli = Cli::parse_from(["helix", "sync", "production", "--yes"]);
@@ -490,6 +490,7 @@ fn past_tense(verb: &str) -> String {
         "compiling" => "Compiled",
         "deleting" => "Deleted",
         "deploying" => "Deployed",
+        "importing" => "Imported",
         "initializing" => "Initialized",
         "pruning" => "Pruned",
         "pulling" => "Pulled",
@@ -60,7 +60,7 @@ impl ProjectContext {
     }
 }
 
-fn find_project_root(start: &Path) -> Result<PathBuf, ProjectError> {
+pub(crate) fn find_project_root(start: &Path) -> Result<PathBuf, ProjectError> {
     let mut current = start.to_path_buf();
     loop {
         if current.join("helix.toml").exists() {
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::ffi::OsString;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -18,21 +19,42 @@ const SKILLS_COMMITS_API_URL: &str =
 /// Source identifier recorded in the skills lockfile for the Helix skill pack.
 const HELIX_SKILLS_SOURCE: &str = "HelixDB/skills";
 
+/// Set by the `--no-update-check` flag for the current invocation.
+static UPDATE_CHECK_SKIPPED: AtomicBool = AtomicBool::new(false);
+
+/// Skip the binary and skills update checks for the rest of this process.
+pub fn skip_update_checks() {
+    UPDATE_CHECK_SKIPPED.store(true, Ordering::Relaxed);
+}
+
 /// Returns true when the user has opted out of the background update check via
-/// `HELIX_NO_UPDATE_CHECK` or `HELIX_DISABLE_UPDATE_CHECK`. Lets sandboxes, CI,
-/// and restricted-network environments skip the GitHub API call (and its
-/// up-to-10s timeout) on the first command of a fresh machine.
+/// `--no-update-check`, `HELIX_NO_UPDATE_CHECK`, `HELIX_DISABLE_UPDATE_CHECK`, or
+/// `HELIX_AUTO_UPDATE=off`. Lets sandboxes, CI, and restricted-network
+/// environments skip the GitHub API call (and its up-to-10s timeout) on the
+/// first command of a fresh machine.
 fn update_check_disabled() -> bool {
-    env_disables_update_check(
-        std::env::var_os("HELIX_NO_UPDATE_CHECK"),
-        std::env::var_os("HELIX_DISABLE_UPDATE_CHECK"),
-    )
+    UPDATE_CHECK_SKIPPED.load(Ordering::Relaxed)
+        || env_disables_update_check(
+            std::env::var_os("HELIX_NO_UPDATE_CHECK"),
+            std::env::var_os("HELIX_DISABLE_UPDATE_CHECK"),
+            std::env::var_os("HELIX_AUTO_UPDATE"),
+        )
 }
 
 /// Pure core of [`update_check_disabled`] so the opt-out logic can be unit
 /// tested without mutating process-global environment state.
-fn env_disables_update_check(no_update_check: Option<OsString>, disable: Option<OsString>) -> bool {
-    no_update_check.is_some() || disable.is_some()
+fn env_disables_update_check(
+    no_update_check: Option<OsString>,
+    disable: Option<OsString>,
+    auto_update: Option<OsString>,
+) -> bool {
+    let auto_update_off = auto_update.is_some_and(|value| {
+        matches!(
+            value.to_string_lossy().trim().to_ascii_lowercase().as_str(),
+            "off" | "false" | "0" | "no"
+        )
+    });
+    no_update_check.is_some() || disable.is_some() || auto_update_off
 }
 
 #[derive(Deserialize)]
@@ -95,9 +117,14 @@ fn should_check_for_updates() -> Result<bool> {
     let update_cache: UpdateCache = toml::from_str(&cache_content)?;
 
     let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-    let time_since_check = now.saturating_sub(update_cache.last_check);
+    Ok(!update_cache.is_fresh(now))
+}
 
-    Ok(time_since_check >= UPDATE_CHECK_INTERVAL)
+impl UpdateCache {
+    /// Whether the last check is recent enough to skip hitting GitHub again.
+    fn is_fresh(&self, now: u64) -> bool {
+        now.saturating_sub(self.last_check) < UPDATE_CHECK_INTERVAL
+    }
 }
 
 fn save_update_check(latest_version: Option<String>) -> Result<()> {
@@ -341,16 +368,25 @@ mod tests {
 
     #[test]
     fn update_check_enabled_when_no_env_vars_set() {
-        assert!(!env_disables_update_check(None, None));
+        assert!(!env_disables_update_check(None, None, None));
     }
 
     #[test]
     fn update_check_disabled_by_either_env_var() {
-        assert!(env_disables_update_check(Some(OsString::from("1")), None));
-        assert!(env_disables_update_check(None, Some(OsString::from("1"))));
         assert!(env_disables_update_check(
             Some(OsString::from("1")),
-            Some(OsString::from("1"))
+            None,
+            None
+        ));
+        assert!(env_disables_update_check(
+            None,
+            Some(OsString::from("1")),
+            None
+        ));
+        assert!(env_disables_update_check(
+            Some(OsString::from("1")),
+            Some(OsString::from("1")),
+            None
         ));
     }
 
@@ -358,6 +394,36 @@ mod tests {
     fn update_check_disabled_even_when_value_is_empty() {
         // Presence is what matters (`HELIX_NO_UPDATE_CHECK=` still opts out),
         // matching how `var_os` reports a set-but-empty variable.
-        assert!(env_disables_update_check(Some(OsString::new()), None));
+        assert!(env_disables_update_check(Some(OsString::new()), None, None));
+    }
+
+    #[test]
+    fn auto_update_off_disables_update_check() {
+        for value in ["off", "OFF", "false", "0", "no"] {
+            assert!(
+                env_disables_update_check(None, None, Some(OsString::from(value))),
+                "HELIX_AUTO_UPDATE={value} should disable the check"
+            );
+        }
+        assert!(!env_disables_update_check(
+            None,
+            None,
+            Some(OsString::from("on"))
+        ));
+    }
+
+    #[test]
+    fn update_cache_prevents_more_than_one_check_per_day() {
+        let checked_at = 1_700_000_000;
+        let cache = UpdateCache {
+            last_check: checked_at,
+            latest_version: Some("9.9.9".to_string()),
+        };
+
+        assert!(cache.is_fresh(checked_at));
+        assert!(cache.is_fresh(checked_at + UPDATE_CHECK_INTERVAL - 1));
+        assert!(!cache.is_fresh(checked_at + UPDATE_CHECK_INTERVAL));
+        // A clock that moved backwards keeps using the cache rather than re-checking.
+        assert!(cache.is_fresh(checked_at - 60));
     }
 }
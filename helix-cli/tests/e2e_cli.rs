@@ -62,6 +62,82 @@ fn removed_commands_return_friendly_errors() {
     assert!(deploy.contains("helix push <instance>"));
 }
 
+#[test]
+fn output_json_reports_failures_as_structured_errors() {
+    let fixture = CliFixture::new();
+
+    let compile = stderr(
+        fixture
+            .command()
+            .args(["compile", "--output", "json"])
+            .assert()
+            .failure(),
+    );
+    let error: JsonValue = serde_json::from_str(compile.trim()).expect("error should be JSON");
+    assert_eq!(error["category"], "cli");
+    assert_eq!(error["severity"], "error");
+    assert_eq!(
+        error["message"],
+        "`helix compile` is not a command in HelixDB v2"
+    );
+    assert!(
+        error["hint"]
+            .as_str()
+            .is_some_and(|hint| hint.contains("there is no compile/check step"))
+    );
+
+    let status = stderr(
+        fixture
+            .command()
+            .args(["--output", "json", "status"])
+            .current_dir(fixture.root())
+            .assert()
+            .failure(),
+    );
+    let error: JsonValue = serde_json::from_str(status.trim()).expect("error should be JSON");
+    assert_eq!(error["category"], "project");
+    assert_eq!(error["message"], "project configuration not found");
+    assert!(
+        error["hint"]
+            .as_str()
+            .is_some_and(|hint| hint.contains("helix init"))
+    );
+
+    // A failure before the command runs (here the update check can't create its
+    // cache under ~/.helix) is reported the same way.
+    fs::write(fixture.root().join("home").join(".helix"), "").expect("block ~/.helix");
+    let update_check = stderr(
+        fixture
+            .command()
+            .env_remove("HELIX_NO_UPDATE_CHECK")
+            .env_remove("HELIX_DISABLE_UPDATE_CHECK")
+            .args(["--output", "json", "status"])
+            .current_dir(fixture.root())
+            .assert()
+            .failure(),
+    );
+    let last_line = update_check.lines().last().unwrap_or_default();
+    let error: JsonValue = serde_json::from_str(last_line).expect("error should be JSON");
+    assert_eq!(error["message"], "Failed to check for CLI updates");
+    assert_eq!(error["severity"], "error");
+}
+
+#[test]
+fn doctor_reports_missing_project_and_fails() {
+    let fixture = CliFixture::new();
+
+    let assert = fixture
+        .command()
+        .arg("doctor")
+        .current_dir(fixture.root())
+        .assert()
+        .failure();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    assert!(stdout.contains("Helix Doctor"), "{stdout}");
+    assert!(stdout.contains("no helix.toml found"), "{stdout}");
+    assert!(stderr(assert).contains("failed"));
+}
+
 #[test]
 fn init_and_add_generate_expected_project_files() {
     let fixture = CliFixture::new();
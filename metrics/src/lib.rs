@@ -2,57 +2,65 @@ pub mod events;
 
 use std::{
     cell::RefCell,
+    collections::{HashMap, HashSet},
     env::consts::OS,
     fs,
-    path::Path,
+    path::{Path, PathBuf},
     sync::{
-        LazyLock, OnceLock,
-        atomic::{AtomicUsize, Ordering},
+        Arc, LazyLock, Mutex, OnceLock, RwLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+use hdrhistogram::Histogram;
 use serde::Serialize;
 use tokio::task::JoinHandle;
 
 pub static METRICS_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
 
 static CONFIG: LazyLock<String> = LazyLock::new(|| {
-    let home_dir = std::env::var("HOME").unwrap_or("~/".to_string());
-    let config_path = &format!("{home_dir}/.helix/credentials");
-    let config_path = Path::new(config_path);
-    fs::read_to_string(config_path).unwrap_or_default()
+    credentials_path(|name| std::env::var(name).ok())
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default()
 });
 
-pub static HELIX_USER_ID: LazyLock<&'static str> = LazyLock::new(|| {
-    // read from credentials file
-    for line in CONFIG.lines() {
-        if let Some((key, value)) = line.split_once("=")
-            && key.to_lowercase() == "helix_user_id"
-        {
-            return value;
-        }
+/// Location of the Helix credentials file: `HELIX_CREDENTIALS_PATH` when set,
+/// otherwise `$HOME/.helix/credentials`. Returns `None` when neither is
+/// available, in which case metrics run with their defaults.
+fn credentials_path(lookup: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    if let Some(path) = lookup("HELIX_CREDENTIALS_PATH").filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
     }
-    String::new().leak()
-});
+    let home = lookup("HOME").filter(|home| !home.is_empty())?;
+    Some(Path::new(&home).join(".helix").join("credentials"))
+}
 
-pub static METRICS_ENABLED: LazyLock<bool> = LazyLock::new(|| {
-    for line in CONFIG.lines() {
-        if let Some((key, value)) = line.split_once("=")
-            && key.to_lowercase().as_str() == "metrics"
-        {
-            return value.to_string().parse().unwrap_or(true);
-        }
-    }
-    true
-});
+fn credential_value<'a>(config: &'a str, key: &str) -> Option<&'a str> {
+    config.lines().find_map(|line| {
+        line.split_once('=')
+            .filter(|(name, _)| name.to_lowercase() == key)
+            .map(|(_, value)| value)
+    })
+}
+
+pub static HELIX_USER_ID: LazyLock<&'static str> =
+    LazyLock::new(|| credential_value(&CONFIG, "helix_user_id").unwrap_or_default());
+
+pub static METRICS_ENABLED: LazyLock<bool> = LazyLock::new(|| metrics_enabled(&CONFIG));
+
+fn metrics_enabled(config: &str) -> bool {
+    credential_value(config, "metrics")
+        .map(|value| value.parse().unwrap_or(true))
+        .unwrap_or(true)
+}
 
 pub const METRICS_URL: &str = "https://logs.helix-db.com/v2";
 
 // Thread-local buffer for events
 thread_local! {
     static EVENT_BUFFER: RefCell<Vec<events::RawEvent<events::EventData>>> =
-        RefCell::new(Vec::with_capacity(THREAD_LOCAL_EVENT_BUFFER_LENGTH));
+        RefCell::new(Vec::with_capacity(FLUSH_CONFIG.buffer_capacity));
 }
 
 // Global state for metrics system
@@ -92,10 +100,406 @@ static METRICS_STATE: LazyLock<MetricsState> = LazyLock::new(|| {
 
 // Configuration constants
 const THREAD_LOCAL_EVENT_BUFFER_LENGTH: usize = 4096;
+/// Upper bound for `HELIX_METRICS_BUFFER_CAP`, so a typo can't reserve gigabytes per thread.
+const MAX_EVENT_BUFFER_CAPACITY: usize = 1 << 20;
 const THREAD_LOCAL_FLUSH_THRESHOLD: usize = 2048;
 const BATCH_TIMEOUT_SECS: u64 = 1;
 const THREAD_LOCAL_FLUSH_INTERVAL_SECS: u64 = 1; // Flush thread-local buffers every second
 
+/// Buffering, flush and batching settings, overridable through the environment:
+/// `HELIX_METRICS_BUFFER_CAP`, `HELIX_METRICS_FLUSH_THRESHOLD`,
+/// `HELIX_METRICS_FLUSH_INTERVAL_SECS` and `HELIX_METRICS_BATCH_TIMEOUT_SECS`.
+/// Missing, invalid, zero or out-of-range values fall back to the constants above.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FlushConfig {
+    buffer_capacity: usize,
+    flush_threshold: usize,
+    flush_interval: Duration,
+    batch_timeout: Duration,
+}
+
+impl FlushConfig {
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let buffer_capacity = positive_setting(
+            &lookup,
+            "HELIX_METRICS_BUFFER_CAP",
+            THREAD_LOCAL_EVENT_BUFFER_LENGTH,
+        );
+        let buffer_capacity = if buffer_capacity > MAX_EVENT_BUFFER_CAPACITY {
+            eprintln!(
+                "Ignoring HELIX_METRICS_BUFFER_CAP={buffer_capacity}: maximum is {MAX_EVENT_BUFFER_CAPACITY}, using {THREAD_LOCAL_EVENT_BUFFER_LENGTH}"
+            );
+            THREAD_LOCAL_EVENT_BUFFER_LENGTH
+        } else {
+            buffer_capacity
+        };
+
+        Self {
+            buffer_capacity,
+            flush_threshold: positive_setting(
+                &lookup,
+                "HELIX_METRICS_FLUSH_THRESHOLD",
+                THREAD_LOCAL_FLUSH_THRESHOLD,
+            ),
+            flush_interval: Duration::from_secs(positive_setting(
+                &lookup,
+                "HELIX_METRICS_FLUSH_INTERVAL_SECS",
+                THREAD_LOCAL_FLUSH_INTERVAL_SECS,
+            )),
+            batch_timeout: Duration::from_secs(positive_setting(
+                &lookup,
+                "HELIX_METRICS_BATCH_TIMEOUT_SECS",
+                BATCH_TIMEOUT_SECS,
+            )),
+        }
+    }
+
+    /// Whether a thread-local buffer holding `buffered` events, last flushed
+    /// `since_last_flush` ago, should be flushed now.
+    fn should_flush(&self, buffered: usize, since_last_flush: Duration) -> bool {
+        buffered >= self.flush_threshold || since_last_flush >= self.flush_interval
+    }
+}
+
+fn positive_setting<T>(lookup: &impl Fn(&str) -> Option<String>, name: &str, default: T) -> T
+where
+    T: std::str::FromStr + PartialOrd + Default + std::fmt::Display,
+{
+    let Some(raw) = lookup(name) else {
+        return default;
+    };
+    match raw.trim().parse::<T>() {
+        Ok(value) if value > T::default() => value,
+        _ => {
+            eprintln!("Ignoring {name}={raw}: expected a positive integer, using {default}");
+            default
+        }
+    }
+}
+
+/// Serialization failures tolerated for one event type before it is suppressed.
+const SERIALIZATION_FAILURE_THRESHOLD: u32 = 8;
+/// How long a suppressed event type is dropped before it is given another chance.
+const SERIALIZATION_SUPPRESSION_SECS: u64 = 600;
+
+#[derive(Debug, Default)]
+struct SerializationFailures {
+    count: u32,
+    suppressed_until: Option<Instant>,
+}
+
+/// Serialization failures keyed by [`events::EventType::as_str`].
+static SERIALIZATION_FAILURES: LazyLock<Mutex<HashMap<&'static str, SerializationFailures>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Set while any event type is suppressed, so `log_event` skips the lock otherwise.
+static SUPPRESSION_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Largest latency tracked by the per-query histograms (one minute); slower queries
+/// are recorded at this value.
+const MAX_TRACKED_LATENCY_USEC: u64 = 60_000_000;
+/// Significant decimal digits kept by the per-query histograms. With the bound above
+/// each histogram is about 20 KiB.
+const LATENCY_SIGNIFICANT_DIGITS: u8 = 2;
+/// Distinct query names with a latency histogram; later names aren't tracked.
+const MAX_TRACKED_QUERIES: usize = 256;
+/// Latency histograms across all threads (about 10 MiB); once reached, new
+/// query/thread pairs aren't tracked until exited threads' histograms are merged.
+const MAX_LATENCY_HISTOGRAMS: usize = 512;
+
+/// Live latency histograms, per-thread and retired, at most [`MAX_LATENCY_HISTOGRAMS`].
+static LATENCY_HISTOGRAM_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+type LatencyHistograms = HashMap<String, Histogram<u64>>;
+
+/// `QuerySuccess` latencies keyed by query name, aggregated in-process so they're
+/// available without the remote collector. Each thread records into its own
+/// histograms; [`metrics_stats`] merges them.
+struct LatencyRegistry {
+    /// Histograms of live threads. Each lock is only contended while stats are read.
+    threads: Vec<Arc<Mutex<LatencyHistograms>>>,
+    /// Histograms merged from threads that have exited.
+    retired: LatencyHistograms,
+}
+
+static LATENCY_REGISTRY: LazyLock<Mutex<LatencyRegistry>> = LazyLock::new(|| {
+    Mutex::new(LatencyRegistry {
+        threads: Vec::new(),
+        retired: HashMap::new(),
+    })
+});
+
+/// Query names with a histogram, at most [`MAX_TRACKED_QUERIES`].
+static TRACKED_QUERY_NAMES: LazyLock<RwLock<HashSet<String>>> =
+    LazyLock::new(|| RwLock::new(HashSet::new()));
+
+/// This thread's histograms, registered on first use and merged into
+/// [`LatencyRegistry::retired`] when the thread exits.
+struct ThreadLatencies(Arc<Mutex<LatencyHistograms>>);
+
+impl ThreadLatencies {
+    fn register() -> Self {
+        let histograms = Arc::new(Mutex::new(HashMap::new()));
+        lock_latency_registry()
+            .threads
+            .push(Arc::clone(&histograms));
+        Self(histograms)
+    }
+}
+
+impl Drop for ThreadLatencies {
+    fn drop(&mut self) {
+        let mut registry = lock_latency_registry();
+        registry
+            .threads
+            .retain(|histograms| !Arc::ptr_eq(histograms, &self.0));
+        let histograms = std::mem::take(&mut *lock_histograms(&self.0));
+        for (query_name, histogram) in histograms {
+            if merge_histogram(&mut registry.retired, query_name, &histogram) {
+                LATENCY_HISTOGRAM_COUNT.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+thread_local! {
+    static THREAD_LATENCIES: ThreadLatencies = ThreadLatencies::register();
+}
+
+/// Snapshot of the metrics system's internal counters, see [`metrics_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetricsStats {
+    /// Events that failed to serialize, per event type, since the type was last reset.
+    pub serialization_failures: HashMap<&'static str, u32>,
+    /// Event types currently dropped because they kept failing to serialize.
+    pub suppressed_event_types: Vec<&'static str>,
+    /// Latency distribution of successful queries, per query name.
+    pub query_latencies: HashMap<String, LatencyPercentiles>,
+}
+
+/// Percentiles of one query's recorded latencies, in microseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyPercentiles {
+    pub count: u64,
+    pub p50_usec: u64,
+    pub p95_usec: u64,
+    pub p99_usec: u64,
+}
+
+/// Report the metrics system's internal counters.
+pub fn metrics_stats() -> MetricsStats {
+    let now = Instant::now();
+    let failures = lock_serialization_failures();
+    let mut suppressed_event_types: Vec<&'static str> = failures
+        .iter()
+        .filter(|(_, entry)| entry.suppressed_until.is_some_and(|until| until > now))
+        .map(|(event_type, _)| *event_type)
+        .collect();
+    suppressed_event_types.sort_unstable();
+
+    MetricsStats {
+        serialization_failures: failures
+            .iter()
+            .map(|(event_type, entry)| (*event_type, entry.count))
+            .collect(),
+        suppressed_event_types,
+        query_latencies: query_latency_percentiles(),
+    }
+}
+
+fn lock_latency_registry() -> std::sync::MutexGuard<'static, LatencyRegistry> {
+    LATENCY_REGISTRY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn lock_histograms(
+    histograms: &Mutex<LatencyHistograms>,
+) -> std::sync::MutexGuard<'_, LatencyHistograms> {
+    histograms
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn new_latency_histogram() -> Option<Histogram<u64>> {
+    Histogram::new_with_bounds(1, MAX_TRACKED_LATENCY_USEC, LATENCY_SIGNIFICANT_DIGITS).ok()
+}
+
+/// Whether `query_name` may get a histogram: it already has one, or fewer than
+/// `limit` names are tracked.
+fn admit_query_name(tracked: &mut HashSet<String>, query_name: &str, limit: usize) -> bool {
+    if tracked.contains(query_name) {
+        return true;
+    }
+    if tracked.len() >= limit {
+        return false;
+    }
+    tracked.insert(query_name.to_string());
+    true
+}
+
+/// A name already recorded on this thread only touches the thread's own histograms.
+/// The registry lock is never taken while a thread's histograms are locked, since
+/// [`query_latency_percentiles`] takes them in the other order.
+fn record_query_latency(query_name: &str, time_taken_usec: u32) {
+    let _ = THREAD_LATENCIES.try_with(|latencies| {
+        let latency_usec = u64::from(time_taken_usec).max(1);
+        if let Some(histogram) = lock_histograms(&latencies.0).get_mut(query_name) {
+            histogram.saturating_record(latency_usec);
+            return;
+        }
+
+        let known = TRACKED_QUERY_NAMES
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .contains(query_name);
+        let admitted = known
+            || admit_query_name(
+                &mut TRACKED_QUERY_NAMES
+                    .write()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()),
+                query_name,
+                MAX_TRACKED_QUERIES,
+            );
+        if !admitted || !reserve_slot(&LATENCY_HISTOGRAM_COUNT, MAX_LATENCY_HISTOGRAMS) {
+            return;
+        }
+        let Some(mut histogram) = new_latency_histogram() else {
+            LATENCY_HISTOGRAM_COUNT.fetch_sub(1, Ordering::Relaxed);
+            return;
+        };
+        histogram.saturating_record(latency_usec);
+        lock_histograms(&latencies.0).insert(query_name.to_string(), histogram);
+    });
+}
+
+/// Take one of `limit` slots counted by `count`; false when none are left.
+fn reserve_slot(count: &AtomicUsize, limit: usize) -> bool {
+    count
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |taken| {
+            (taken < limit).then_some(taken + 1)
+        })
+        .is_ok()
+}
+
+/// Add `histogram` to the entry for `query_name`, or insert a copy. Returns true
+/// when it was added to an existing entry.
+fn merge_histogram(
+    into: &mut LatencyHistograms,
+    query_name: String,
+    histogram: &Histogram<u64>,
+) -> bool {
+    if let Some(merged) = into.get_mut(&query_name) {
+        let _ = merged.add(histogram);
+        true
+    } else {
+        into.insert(query_name, histogram.clone());
+        false
+    }
+}
+
+fn query_latency_percentiles() -> HashMap<String, LatencyPercentiles> {
+    let registry = lock_latency_registry();
+    let mut merged = registry.retired.clone();
+    for histograms in &registry.threads {
+        for (query_name, histogram) in lock_histograms(histograms).iter() {
+            merge_histogram(&mut merged, query_name.clone(), histogram);
+        }
+    }
+    drop(registry);
+
+    merged
+        .into_iter()
+        .map(|(query_name, histogram)| {
+            (
+                query_name,
+                LatencyPercentiles {
+                    count: histogram.len(),
+                    p50_usec: histogram.value_at_quantile(0.50),
+                    p95_usec: histogram.value_at_quantile(0.95),
+                    p99_usec: histogram.value_at_quantile(0.99),
+                },
+            )
+        })
+        .collect()
+}
+
+fn lock_serialization_failures()
+-> std::sync::MutexGuard<'static, HashMap<&'static str, SerializationFailures>> {
+    SERIALIZATION_FAILURES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Whether events of this type are currently dropped. An expired suppression is
+/// cleared here so the type starts over with a fresh failure count.
+fn is_event_type_suppressed(event_type: &events::EventType) -> bool {
+    if !SUPPRESSION_ACTIVE.load(Ordering::Relaxed) {
+        return false;
+    }
+    let mut failures = lock_serialization_failures();
+    let Some(entry) = failures.get_mut(event_type.as_str()) else {
+        return false;
+    };
+    match entry.suppressed_until {
+        Some(until) if until > Instant::now() => true,
+        Some(_) => {
+            *entry = SerializationFailures::default();
+            let still_active = failures
+                .values()
+                .any(|entry| entry.suppressed_until.is_some());
+            SUPPRESSION_ACTIVE.store(still_active, Ordering::Relaxed);
+            false
+        }
+        None => false,
+    }
+}
+
+fn record_serialization_failure(event_type: &events::EventType, error: &sonic_rs::Error) {
+    let mut failures = lock_serialization_failures();
+    let entry = failures.entry(event_type.as_str()).or_default();
+    if entry.suppressed_until.is_some() {
+        return;
+    }
+    entry.count += 1;
+    if entry.count >= SERIALIZATION_FAILURE_THRESHOLD {
+        entry.suppressed_until =
+            Some(Instant::now() + Duration::from_secs(SERIALIZATION_SUPPRESSION_SECS));
+        SUPPRESSION_ACTIVE.store(true, Ordering::Relaxed);
+        eprintln!(
+            "Failed to serialize {} {} events; suppressing them for {}s: {}",
+            entry.count,
+            event_type.as_str(),
+            SERIALIZATION_SUPPRESSION_SECS,
+            error
+        );
+    } else {
+        eprintln!("Failed to serialize event: {}", error);
+    }
+}
+
+/// Serialize events as NDJSON (newline-delimited JSON), one object per line.
+/// Events that fail to serialize are skipped and counted against their type.
+fn encode_ndjson<D>(events: &[events::RawEvent<D>]) -> String
+where
+    D: Serialize + std::fmt::Debug + Clone,
+{
+    let mut ndjson = String::with_capacity(events.len() * 256);
+    for event in events {
+        match sonic_rs::to_string(event) {
+            Ok(json) => {
+                ndjson.push_str(&json);
+                ndjson.push('\n');
+            }
+            Err(e) => record_serialization_failure(&event.event_type, &e),
+        }
+    }
+    ndjson
+}
+
+static FLUSH_CONFIG: LazyLock<FlushConfig> =
+    LazyLock::new(|| FlushConfig::from_lookup(|name| std::env::var(name).ok()));
+
 /// Initialize the metrics system with a tokio runtime
 /// This must be called once at startup with an active tokio runtime
 pub fn init_metrics_system() {
@@ -125,15 +529,23 @@ pub fn init_thread_local() {
         return;
     }
 
-    EVENT_BUFFER.with(|buffer| {
-        buffer.borrow_mut().clear();
-    });
+    EVENT_BUFFER
+        .with(|buffer| reset_event_buffer(&mut buffer.borrow_mut(), FLUSH_CONFIG.buffer_capacity));
 
     LAST_FLUSH_TIME.with(|time| {
         *time.borrow_mut() = std::time::Instant::now();
     });
 }
 
+/// Empty `buf`, reallocating it unless it already has exactly `capacity`.
+fn reset_event_buffer(buf: &mut Vec<events::RawEvent<events::EventData>>, capacity: usize) {
+    if buf.capacity() == capacity {
+        buf.clear();
+    } else {
+        *buf = Vec::with_capacity(capacity);
+    }
+}
+
 /// Set the batch threshold for notifications
 /// When the number of batches in channel exceeds this, sender task is notified
 pub fn set_threshold_batches(batches: usize) {
@@ -153,21 +565,27 @@ pub fn log_event<D>(event_type: events::EventType, event_data: D)
 where
     D: Into<events::EventData> + Serialize + std::fmt::Debug + Clone,
 {
+    let event_data = event_data.into();
     if !*METRICS_ENABLED {
         return;
     }
+    // Recorded before the suppression check: percentiles don't need serialization.
+    if let events::EventData::QuerySuccess(query) = &event_data {
+        record_query_latency(&query.query_name, query.time_taken_usec);
+    }
+    if is_event_type_suppressed(&event_type) {
+        return;
+    }
 
-    let raw_event = create_raw_event(event_type, event_data.into());
+    let raw_event = create_raw_event(event_type, event_data);
 
     EVENT_BUFFER.with(|buffer| {
         let mut buf = buffer.borrow_mut();
         buf.push(raw_event);
 
         // Check if we should flush based on size or time
-        let should_flush = buf.len() >= THREAD_LOCAL_FLUSH_THRESHOLD
-            || LAST_FLUSH_TIME.with(|time| {
-                time.borrow().elapsed() >= Duration::from_secs(THREAD_LOCAL_FLUSH_INTERVAL_SECS)
-            });
+        let should_flush = LAST_FLUSH_TIME
+            .with(|time| FLUSH_CONFIG.should_flush(buf.len(), time.borrow().elapsed()));
 
         if should_flush {
             flush_local_buffer(&mut buf);
@@ -180,12 +598,13 @@ where
 
 /// Flush the thread-local buffer to the global channel
 fn flush_local_buffer(buf: &mut Vec<events::RawEvent<events::EventData>>) {
-    let events = std::mem::take(buf);
-
-    if events.is_empty() {
+    if buf.is_empty() {
         return;
     }
 
+    // Hand off the full buffer and keep the configured capacity for the next batch.
+    let events = std::mem::replace(buf, Vec::with_capacity(FLUSH_CONFIG.buffer_capacity));
+
     // Send entire vec in one operation - much faster!
     let _ = METRICS_STATE.events_tx.send(events);
 
@@ -228,7 +647,7 @@ async fn sender_task(
             _ = notify_rx.recv_async() => {
                 process_batch(&events_rx).await;
             }
-            _ = tokio::time::sleep(Duration::from_secs(BATCH_TIMEOUT_SECS)) => {
+            _ = tokio::time::sleep(FLUSH_CONFIG.batch_timeout) => {
                 // Periodic flush even if threshold not reached
                 process_batch(&events_rx).await;
             }
@@ -255,22 +674,7 @@ async fn process_batch(
     // Spawn new task for serialization + HTTP
     // This allows the sender task to continue processing batches
     Some(tokio::spawn(async move {
-        // Serialize as NDJSON (newline-delimited JSON)
-        // Each event is a separate JSON object on its own line
-        let mut ndjson = String::with_capacity(events.len() * 256);
-        for event in &events {
-            match sonic_rs::to_string(event) {
-                Ok(json) => {
-                    ndjson.push_str(&json);
-                    ndjson.push('\n');
-                }
-                Err(e) => {
-                    eprintln!("Failed to serialize event: {}", e);
-                    continue;
-                }
-            }
-        }
-
+        let ndjson = encode_ndjson(&events);
         if ndjson.is_empty() {
             return;
         }
@@ -378,6 +782,41 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_event_buffer_keeps_configured_capacity_after_flush() {
+        // What `init_thread_local` does when metrics are enabled.
+        EVENT_BUFFER.with(|buffer| {
+            let mut buf = buffer.borrow_mut();
+            *buf = Vec::new();
+            reset_event_buffer(&mut buf, FLUSH_CONFIG.buffer_capacity);
+            assert_eq!(buf.capacity(), FLUSH_CONFIG.buffer_capacity);
+
+            buf.push(create_raw_event(
+                events::EventType::QuerySuccess,
+                events::EventData::QuerySuccess(events::QuerySuccessEvent {
+                    cluster_id: None,
+                    query_name: "test_query".to_string(),
+                    time_taken_usec: 1000,
+                }),
+            ));
+            flush_local_buffer(&mut buf);
+            assert!(buf.is_empty());
+            assert_eq!(buf.capacity(), FLUSH_CONFIG.buffer_capacity);
+        });
+
+        let config = FlushConfig::from_lookup(|name| {
+            (name == "HELIX_METRICS_BUFFER_CAP").then(|| "256".to_string())
+        });
+        assert_eq!(config.buffer_capacity, 256);
+
+        for out_of_range in ["0", "-5", "2000000"] {
+            let config = FlushConfig::from_lookup(|name| {
+                (name == "HELIX_METRICS_BUFFER_CAP").then(|| out_of_range.to_string())
+            });
+            assert_eq!(config.buffer_capacity, THREAD_LOCAL_EVENT_BUFFER_LENGTH);
+        }
+    }
+
     #[test]
     fn test_thread_local_buffering() {
         init_thread_local();
@@ -412,8 +851,8 @@ mod tests {
         // Clear the channel first
         while METRICS_STATE.events_rx.try_recv().is_ok() {}
 
-        // Log exactly THREAD_LOCAL_FLUSH_THRESHOLD events to trigger flush
-        for i in 0..THREAD_LOCAL_FLUSH_THRESHOLD {
+        // Log exactly flush_threshold events to trigger flush
+        for i in 0..FLUSH_CONFIG.flush_threshold {
             log_event(
                 events::EventType::Test,
                 events::TestEvent {
@@ -432,7 +871,7 @@ mod tests {
             assert_eq!(buffer.borrow().len(), 0);
         });
 
-        // At least 1 batch should have been added (since we logged THREAD_LOCAL_FLUSH_THRESHOLD events)
+        // At least 1 batch should have been added (since we logged flush_threshold events)
         let channel_count = METRICS_STATE.events_rx.len();
         assert!(
             channel_count >= 1,
@@ -466,7 +905,7 @@ mod tests {
         set_threshold_batches(1);
 
         // Log enough events to trigger a flush (which sends 1 batch)
-        for i in 0..THREAD_LOCAL_FLUSH_THRESHOLD {
+        for i in 0..FLUSH_CONFIG.flush_threshold {
             log_event(
                 events::EventType::Test,
                 events::TestEvent {
@@ -488,6 +927,138 @@ mod tests {
         set_threshold_batches(num_cpus::get());
     }
 
+    #[test]
+    fn test_flush_config_defaults_and_env_overrides() {
+        let defaults = FlushConfig::from_lookup(|_| None);
+        assert_eq!(defaults.flush_threshold, THREAD_LOCAL_FLUSH_THRESHOLD);
+        assert_eq!(
+            defaults.flush_interval,
+            Duration::from_secs(THREAD_LOCAL_FLUSH_INTERVAL_SECS)
+        );
+        assert_eq!(
+            defaults.batch_timeout,
+            Duration::from_secs(BATCH_TIMEOUT_SECS)
+        );
+
+        let config = FlushConfig::from_lookup(|name| match name {
+            "HELIX_METRICS_FLUSH_THRESHOLD" => Some("16".to_string()),
+            "HELIX_METRICS_FLUSH_INTERVAL_SECS" => Some("30".to_string()),
+            "HELIX_METRICS_BATCH_TIMEOUT_SECS" => Some("5".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.flush_threshold, 16);
+        assert_eq!(config.flush_interval, Duration::from_secs(30));
+        assert_eq!(config.batch_timeout, Duration::from_secs(5));
+        assert!(!config.should_flush(15, Duration::ZERO));
+        assert!(config.should_flush(16, Duration::ZERO));
+    }
+
+    #[test]
+    fn test_flush_config_rejects_non_positive_values() {
+        let config = FlushConfig::from_lookup(|name| match name {
+            "HELIX_METRICS_FLUSH_THRESHOLD" => Some("0".to_string()),
+            "HELIX_METRICS_FLUSH_INTERVAL_SECS" => Some("-1".to_string()),
+            "HELIX_METRICS_BATCH_TIMEOUT_SECS" => Some("soon".to_string()),
+            _ => None,
+        });
+        assert_eq!(config, FlushConfig::from_lookup(|_| None));
+    }
+
+    #[test]
+    fn test_low_flush_interval_triggers_time_based_flush() {
+        let config = FlushConfig::from_lookup(|name| {
+            (name == "HELIX_METRICS_FLUSH_INTERVAL_SECS").then(|| "1".to_string())
+        });
+
+        // A single buffered event is far below the size threshold, so only
+        // the elapsed time can trigger the flush.
+        assert!(!config.should_flush(1, Duration::from_millis(500)));
+        assert!(config.should_flush(1, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_credentials_path_override() {
+        let path = credentials_path(|name| match name {
+            "HELIX_CREDENTIALS_PATH" => Some("/run/secrets/helix".to_string()),
+            "HOME" => Some("/home/helix".to_string()),
+            _ => None,
+        });
+        assert_eq!(path, Some(PathBuf::from("/run/secrets/helix")));
+
+        let path = credentials_path(|name| (name == "HOME").then(|| "/home/helix".to_string()));
+        assert_eq!(path, Some(PathBuf::from("/home/helix/.helix/credentials")));
+    }
+
+    #[test]
+    fn test_missing_home_uses_metrics_defaults() {
+        assert_eq!(credentials_path(|_| None), None);
+        assert_eq!(
+            credentials_path(|name| (name == "HOME").then(String::new)),
+            None
+        );
+
+        // No credentials file means metrics stay enabled with no user id.
+        assert!(metrics_enabled(""));
+        assert_eq!(credential_value("", "helix_user_id"), None);
+    }
+
+    #[test]
+    fn test_credentials_file_values() {
+        let config = "helix_user_id=user-123\nhelix_admin_key=secret\nmetrics=false\n";
+        assert_eq!(credential_value(config, "helix_user_id"), Some("user-123"));
+        assert!(!metrics_enabled(config));
+    }
+
+    #[derive(Debug, Clone)]
+    struct UnserializableData;
+
+    impl Serialize for UnserializableData {
+        fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("unserializable test payload"))
+        }
+    }
+
+    #[test]
+    fn test_serialization_failures_are_counted_and_suppress_event_type() {
+        // ReadError is not logged by any other test, so its counter is ours alone.
+        let event_type = events::EventType::ReadError;
+        let broken = events::RawEvent {
+            os: OS,
+            event_type: event_type.clone(),
+            event_data: UnserializableData,
+            user_id: None,
+            email: None,
+            timestamp: 0,
+        };
+
+        assert_eq!(encode_ndjson(std::slice::from_ref(&broken)), "");
+        assert_eq!(
+            metrics_stats().serialization_failures.get("read_error"),
+            Some(&1)
+        );
+        assert!(!is_event_type_suppressed(&event_type));
+
+        let batch = vec![broken; SERIALIZATION_FAILURE_THRESHOLD as usize];
+        assert_eq!(encode_ndjson(&batch), "");
+
+        let stats = metrics_stats();
+        assert_eq!(
+            stats.serialization_failures.get("read_error"),
+            Some(&SERIALIZATION_FAILURE_THRESHOLD)
+        );
+        assert!(stats.suppressed_event_types.contains(&"read_error"));
+        assert!(is_event_type_suppressed(&event_type));
+
+        // Other event types keep serializing normally.
+        let ok = create_raw_event(
+            events::EventType::Test,
+            events::EventData::Test(events::TestEvent::default()),
+        );
+        assert!(encode_ndjson(&[ok]).ends_with('\n'));
+
+        lock_serialization_failures().remove("read_error");
+    }
+
     #[test]
     fn test_create_raw_event() {
         let event = create_raw_event(
@@ -646,6 +1217,46 @@ mod tests {
         assert!(METRICS_STATE.sender_handle.get().is_some());
     }
 
+    #[test]
+    fn test_query_latency_percentiles() {
+        for ms in 1..=100u32 {
+            record_query_latency("latency_percentiles_test", ms * 1000);
+        }
+
+        // Latencies recorded on a thread that has exited are kept.
+        thread::spawn(|| record_query_latency("latency_percentiles_test", 50_000))
+            .join()
+            .unwrap();
+
+        let stats = metrics_stats();
+        let latency = stats.query_latencies["latency_percentiles_test"];
+        assert_eq!(latency.count, 101);
+        // Histogram buckets keep two significant digits.
+        let close = |actual: u64, expected: u64| actual.abs_diff(expected) <= expected / 100 + 1;
+        assert!(close(latency.p50_usec, 50_000), "{latency:?}");
+        assert!(close(latency.p95_usec, 95_000), "{latency:?}");
+        assert!(close(latency.p99_usec, 99_000), "{latency:?}");
+    }
+
+    #[test]
+    fn test_tracked_query_names_are_capped() {
+        let mut tracked = HashSet::new();
+        assert!(admit_query_name(&mut tracked, "a", 2));
+        assert!(admit_query_name(&mut tracked, "b", 2));
+        assert!(!admit_query_name(&mut tracked, "c", 2));
+        assert!(admit_query_name(&mut tracked, "a", 2));
+        assert_eq!(tracked.len(), 2);
+    }
+
+    #[test]
+    fn test_latency_histogram_slots_are_capped() {
+        let count = AtomicUsize::new(0);
+        assert!(reserve_slot(&count, 2));
+        assert!(reserve_slot(&count, 2));
+        assert!(!reserve_slot(&count, 2));
+        assert_eq!(count.load(AtomicOrdering::Relaxed), 2);
+    }
+
     #[test]
     fn test_event_serialization() {
         let event = create_raw_event(